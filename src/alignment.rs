@@ -0,0 +1,245 @@
+use anyhow::Result;
+
+use crate::datatypes::{Angle, Station};
+use crate::horizontal::calculate::{HorizontalCurve, HorizontalDimensions};
+use crate::horizontal::coordinates::central_angle_from_bearings;
+
+const BEARING_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone)]
+pub enum Segment {
+    Tangent { length: f64, bearing: Angle },
+    Curve(Box<HorizontalCurve>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Alignment {
+    pub start_station: f64,
+    pub segments: Vec<Segment>,
+}
+
+impl Segment {
+    /// Tangent length or curve arc length.
+    pub fn length(&self) -> f64 {
+        match self {
+            Segment::Tangent { length, .. } => *length,
+            Segment::Curve(curve) => curve.dimensions.curve_length,
+        }
+    }
+}
+
+impl Alignment {
+    pub fn total_length(&self) -> f64 {
+        self.segments.iter().map(Segment::length).sum()
+    }
+
+    pub fn end_station(&self) -> Station {
+        Station {
+            value: self.start_station + self.total_length(),
+            ..Default::default()
+        }
+    }
+
+    /// Fits a curve of `radius` at the PI where tangent segments `pi_index - 1` and `pi_index` meet,
+    /// trimming both tangents back to the PC and PT and re-stationing every curve downstream.
+    pub fn fit_curve_at_pi(&mut self, pi_index: usize, radius: f64) -> Result<()> {
+        if radius <= 0.0 {
+            return Err(Error::NonPositiveRadius.into());
+        }
+        let (
+            Some(Segment::Tangent {
+                length: back_length,
+                bearing: incoming,
+            }),
+            Some(Segment::Tangent {
+                length: ahead_length,
+                bearing: outgoing,
+            }),
+        ) = (
+            pi_index.checked_sub(1).and_then(|i| self.segments.get(i)),
+            self.segments.get(pi_index),
+        )
+        else {
+            return Err(Error::NotAPi(pi_index).into());
+        };
+        let (curve_angle, turn) = central_angle_from_bearings(*incoming, *outgoing);
+        if curve_angle.radians < BEARING_TOLERANCE {
+            return Err(Error::NoDeflection(pi_index).into());
+        }
+        let dimensions = HorizontalDimensions::from_radius_curve_angle(radius, curve_angle);
+        if dimensions.tangent > *back_length || dimensions.tangent > *ahead_length {
+            return Err(Error::TangentTooShort(pi_index).into());
+        }
+
+        let (back_length, ahead_length) = (*back_length, *ahead_length);
+        if let Segment::Tangent { length, .. } = &mut self.segments[pi_index - 1] {
+            *length = back_length - dimensions.tangent;
+        }
+        if let Segment::Tangent { length, .. } = &mut self.segments[pi_index] {
+            *length = ahead_length - dimensions.tangent;
+        }
+        let curve = HorizontalCurve {
+            turn,
+            ..HorizontalCurve::from_dimensions(dimensions, 0.0)
+        };
+        self.segments
+            .insert(pi_index, Segment::Curve(Box::new(curve)));
+        self.restation();
+
+        Ok(())
+    }
+
+    /// Places every curve on the running stationing from `start_station`.
+    fn restation(&mut self) {
+        let mut station = self.start_station;
+        for segment in &mut self.segments {
+            if let Segment::Curve(curve) = segment {
+                **curve = HorizontalCurve {
+                    turn: curve.turn,
+                    ..HorizontalCurve::from_dimensions(curve.dimensions, station)
+                };
+            }
+            station += segment.length();
+        }
+    }
+
+    /// Merges consecutive tangents sharing a bearing into one segment of their combined length.
+    pub fn simplify(&mut self) {
+        let mut simplified: Vec<Segment> = Vec::with_capacity(self.segments.len());
+
+        for segment in self.segments.drain(..) {
+            if let (
+                Some(Segment::Tangent {
+                    length: previous_length,
+                    bearing: previous_bearing,
+                }),
+                Segment::Tangent { length, bearing },
+            ) = (simplified.last_mut(), &segment)
+            {
+                if (previous_bearing.radians - bearing.radians).abs() < BEARING_TOLERANCE {
+                    *previous_length += *length;
+                    continue;
+                }
+            }
+            simplified.push(segment);
+        }
+
+        self.segments = simplified;
+    }
+}
+
+/// Alignment Errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Segments on both sides of the PI must be tangents.
+    #[error("PI {0} isn't between two tangents.")]
+    NotAPi(usize),
+    /// Tangents meet without deflecting.
+    #[error("Tangents at PI {0} are collinear, no curve is needed.")]
+    NoDeflection(usize),
+    /// Curve tangent runs past the end of a neighbouring tangent.
+    #[error("Tangents at PI {0} are too short for the curve.")]
+    TangentTooShort(usize),
+    /// Radius must be greater than zero.
+    #[error("Radius must be greater than zero.")]
+    NonPositiveRadius,
+}
+
+#[cfg(test)]
+mod alignment_tests {
+    use super::{Alignment, Segment};
+    use crate::datatypes::Angle;
+    use crate::horizontal::calculate::{HorizontalCurve, HorizontalDimensions};
+    use crate::horizontal::TurnDirection;
+
+    #[test]
+    fn simplify_collinear_tangents() {
+        let bearing = Angle { radians: 0.5 };
+        let mut alignment = Alignment {
+            start_station: 1000.,
+            segments: vec![
+                Segment::Tangent {
+                    length: 100.,
+                    bearing,
+                },
+                Segment::Tangent {
+                    length: 250.,
+                    bearing,
+                },
+                Segment::Tangent {
+                    length: 50.,
+                    bearing,
+                },
+            ],
+        };
+        alignment.simplify();
+
+        assert_eq!(alignment.segments.len(), 1);
+        match alignment.segments[0] {
+            Segment::Tangent { length, .. } => assert_eq!(length, 400.),
+            _ => panic!("Expected a tangent."),
+        }
+        assert_eq!(alignment.start_station, 1000.);
+    }
+
+    #[test]
+    fn end_station_sums_segments() {
+        let curve = HorizontalCurve::from_dimensions(
+            HorizontalDimensions::from_radius_curve_angle(818.5, Angle { radians: 0.5 }),
+            1400.,
+        );
+        let alignment = Alignment {
+            start_station: 1000.,
+            segments: vec![
+                Segment::Tangent {
+                    length: 400.,
+                    bearing: Angle { radians: 0.0 },
+                },
+                Segment::Curve(Box::new(curve)),
+                Segment::Tangent {
+                    length: 300.,
+                    bearing: Angle { radians: 0.5 },
+                },
+            ],
+        };
+
+        assert_eq!(alignment.total_length(), 700. + 818.5 * 0.5);
+        assert_eq!(alignment.end_station().value, 1000. + 700. + 409.25);
+    }
+
+    #[test]
+    fn fit_curve_at_middle_pi() -> anyhow::Result<()> {
+        let tangent = |length: f64, degrees: f64| Segment::Tangent {
+            length,
+            bearing: Angle {
+                radians: degrees.to_radians(),
+            },
+        };
+        let mut alignment = Alignment {
+            start_station: 1000.,
+            segments: vec![tangent(1000., 0.), tangent(1000., 30.), tangent(1000., 10.)],
+        };
+        alignment.fit_curve_at_pi(2, 500.)?;
+        alignment.fit_curve_at_pi(1, 1000.)?;
+
+        let Segment::Curve(first) = &alignment.segments[1] else {
+            panic!("Expected a curve.");
+        };
+        let Segment::Curve(second) = &alignment.segments[3] else {
+            panic!("Expected a curve.");
+        };
+        assert_eq!(first.turn, TurnDirection::Right);
+        assert_eq!(second.turn, TurnDirection::Left);
+        let first_tangent = 1000. * 15f64.to_radians().tan();
+        assert!((first.stations.pc.value - (2000. - first_tangent)).abs() < 1e-9);
+        assert!((first.stations.pi.value - 2000.).abs() < 1e-9);
+        assert!(
+            (second.stations.pc.value - (first.stations.pt.value + alignment.segments[2].length()))
+                .abs()
+                < 1e-9
+        );
+        assert!(alignment.fit_curve_at_pi(0, 500.).is_err());
+        assert!(alignment.fit_curve_at_pi(1, 500.).is_err());
+        Ok(())
+    }
+}
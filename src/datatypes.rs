@@ -5,6 +5,8 @@ use anyhow::Result;
 
 use crate::vertical::ObstacleType;
 
+const MPH_PER_KPH: f64 = 0.621371;
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Station {
     pub value: f64,
@@ -15,26 +17,58 @@ pub struct Station {
 
 impl fmt::Display for Station {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
-        let mut cohesive_sta = format!(
-            "STA: {:.0}+{:.2}",
-            (self.value / 100.0).trunc(),
-            (self.value - (self.value / 100.0).trunc() * 100.0).abs()
-        );
-        if let Some(elevation) = self.elevation {
-            cohesive_sta += format!(" ELEV: {:.2}", elevation).as_str();
-        }
-        if let Some(deflection) = self.deflection {
-            cohesive_sta += format!(" DEFL: {:.2}", deflection).as_str();
-        }
-        if let Some(chord) = self.chord {
-            cohesive_sta += format!(" CHOR: {:.2}", chord).as_str();
-        }
+        write!(f, "{}", StationFmt(*self, 2))
+    }
+}
 
-        write!(f, "{}", cohesive_sta.as_str())?;
-        Ok(())
+/// Formats a station with `digits` digits after the "+", e.g. 2 for 10+00 or 3 for 1+000.
+#[derive(Debug, Clone, Copy)]
+pub struct StationFmt(pub Station, pub u32);
+
+impl fmt::Display for StationFmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let StationFmt(station, digits) = *self;
+        write_station(f, station, digits, 2)
     }
 }
 
+/// Formats a metric station in `km+m` notation to the millimetre, e.g. 12+345.678.
+#[derive(Debug, Clone, Copy)]
+pub struct MetricStationFmt(pub Station);
+
+impl fmt::Display for MetricStationFmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        write_station(f, self.0, 3, 3)
+    }
+}
+
+fn write_station(
+    f: &mut std::fmt::Formatter<'_>,
+    station: Station,
+    digits: u32,
+    decimals: usize,
+) -> fmt::Result {
+    let base = 10f64.powi(digits as i32);
+    let mut cohesive_sta = format!(
+        "STA: {:.0}+{:0width$.decimals$}",
+        (station.value / base).trunc(),
+        (station.value - (station.value / base).trunc() * base).abs(),
+        width = digits as usize + decimals + 1
+    );
+    if let Some(elevation) = station.elevation {
+        cohesive_sta += format!(" ELEV: {:.2}", elevation).as_str();
+    }
+    if let Some(deflection) = station.deflection {
+        cohesive_sta += format!(" DEFL: {:.2}", deflection).as_str();
+    }
+    if let Some(chord) = station.chord {
+        cohesive_sta += format!(" CHOR: {:.2}", chord).as_str();
+    }
+
+    write!(f, "{}", cohesive_sta.as_str())?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CurveDetail {
     pub interval: Vec<Station>,
@@ -70,6 +104,15 @@ pub struct Angle {
     pub radians: f64,
 }
 
+/// Unit angles are written in for tables and reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AngleFormat {
+    #[default]
+    Dms,
+    /// Gradians, 400 gon to the circle.
+    Gon,
+}
+
 impl fmt::Display for Angle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -84,7 +127,13 @@ impl fmt::Display for Angle {
 }
 
 impl Angle {
+    /// Parses DMS ("63d15'34\"") when any of `d`, `'` or `"` is present, otherwise decimal degrees ("63.26").
     pub fn from(raw_data: &str) -> Result<Self> {
+        // A leading '-' signs the whole angle, so "-30d15'" is -30.25°.
+        let (sign, raw_data) = match raw_data.trim().strip_prefix('-') {
+            Some(magnitude) => (-1.0, magnitude),
+            None => (1.0, raw_data),
+        };
         if !raw_data.is_empty() {
             if raw_data.chars().any(|c| matches!(c, 'd' | '\'' | '\"')) {
                 let parts = raw_data
@@ -104,21 +153,31 @@ impl Angle {
                 }
 
                 if decimal_degrees >= 180. {
-                    return Err(Error::OversizedAngle.into());
+                    return Err(Error::OversizedAngle {
+                        degrees: sign * decimal_degrees,
+                    }
+                    .into());
                 }
 
                 return Ok(Angle {
-                    radians: decimal_degrees * PI / 180.0,
+                    radians: sign * decimal_degrees * PI / 180.0,
                 });
-            } else if raw_data.chars().all(|c| matches!(c, '0'..='9' | '.')) {
+            } else if raw_data
+                .trim()
+                .chars()
+                .all(|c| matches!(c, '0'..='9' | '.'))
+            {
                 let decimal_degrees = raw_data.trim().parse::<f64>()?;
 
                 if decimal_degrees >= 180. {
-                    return Err(Error::OversizedAngle.into());
+                    return Err(Error::OversizedAngle {
+                        degrees: sign * decimal_degrees,
+                    }
+                    .into());
                 }
 
                 return Ok(Angle {
-                    radians: decimal_degrees * PI / 180.0,
+                    radians: sign * decimal_degrees * PI / 180.0,
                 });
             }
         }
@@ -140,11 +199,101 @@ impl Angle {
     }
 
     pub fn to_decimal_degrees(&self) -> f64 {
-        return self.radians * 180. / PI;
+        self.radians * 180. / PI
+    }
+
+    pub fn to_gon(&self) -> f64 {
+        self.radians * 200. / PI
     }
+
+    pub fn format(&self, angle_format: AngleFormat) -> String {
+        match angle_format {
+            AngleFormat::Dms => self.to_dms(),
+            AngleFormat::Gon => format!("{:.4} gon", self.to_gon()),
+        }
+    }
+
+    /// Total of a deflection series.
+    pub fn sum(angles: &[Angle]) -> Angle {
+        Angle {
+            radians: angles.iter().map(|angle| angle.radians).sum(),
+        }
+    }
+
+    pub fn scaled(&self, factor: f64) -> Angle {
+        Angle {
+            radians: self.radians * factor,
+        }
+    }
+
+    /// Quadrant bearing of this azimuth to the nearest second, e.g. `N 45°30'00" E`.
+    pub fn to_bearing_string(&self) -> String {
+        let azimuth = self.to_decimal_degrees().rem_euclid(360.);
+        let (north_south, angle, east_west) = match azimuth {
+            a if a <= 90. => ('N', a, 'E'),
+            a if a <= 180. => ('S', 180. - a, 'E'),
+            a if a <= 270. => ('S', a - 180., 'W'),
+            a => ('N', 360. - a, 'W'),
+        };
+        let total_seconds = (angle * 3600.).round() as u32;
+
+        format!(
+            "{} {}°{:02}'{:02}\" {}",
+            north_south,
+            total_seconds / 3600,
+            total_seconds / 60 % 60,
+            total_seconds % 60,
+            east_west
+        )
+    }
+
+    /// Parses a quadrant bearing such as `N 45°30'00" E` or `S45d30'W` into an azimuth.
+    pub fn from_bearing(raw_data: &str) -> Result<Self> {
+        let raw_data = raw_data.trim();
+        let mut chars = raw_data.chars();
+        let (Some(north_south), Some(east_west)) = (chars.next(), chars.next_back()) else {
+            return Err(Error::ParseBearing.into());
+        };
+        let angle = Angle::from(chars.as_str().trim().replace('°', "d").as_str())
+            .map_err(|_| Error::ParseBearing)?
+            .to_decimal_degrees();
+        if angle > 90. {
+            return Err(Error::ParseBearing.into());
+        }
+        let azimuth = match (
+            north_south.to_ascii_uppercase(),
+            east_west.to_ascii_uppercase(),
+        ) {
+            ('N', 'E') => angle,
+            ('S', 'E') => 180. - angle,
+            ('S', 'W') => 180. + angle,
+            ('N', 'W') => (360. - angle).rem_euclid(360.),
+            _ => return Err(Error::ParseBearing.into()),
+        };
+
+        Ok(Angle {
+            radians: azimuth * PI / 180.0,
+        })
+    }
+}
+
+/// AASHTO design vehicles, by the Green Book symbol.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DesignVehicle {
+    /// Passenger car.
+    #[default]
+    P,
+    /// Single-unit truck.
+    SU,
+    /// Intermediate semitrailer, 40 ft wheelbase.
+    WB40,
+    /// Interstate semitrailer, 67 ft wheelbase.
+    WB67,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SightType {
     #[default]
     Stopping,
@@ -162,7 +311,8 @@ impl SightType {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DesignStandard {
     AASHTO,
     #[default]
@@ -178,7 +328,161 @@ impl DesignStandard {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnitSystem {
+    #[default]
+    US,
+    Metric,
+}
+
+impl UnitSystem {
+    pub fn next(self) -> Self {
+        match self {
+            Self::US => Self::Metric,
+            Self::Metric => Self::US,
+        }
+    }
+
+    pub fn length_label(self) -> &'static str {
+        match self {
+            Self::US => "ft",
+            Self::Metric => "m",
+        }
+    }
+
+    pub fn speed_label(self) -> &'static str {
+        match self {
+            Self::US => "mph",
+            Self::Metric => "km/h",
+        }
+    }
+
+    /// `C` in `e + f = V^2 / (C * R)`, 15 for mph and ft or 127 for km/h and m.
+    pub fn curvature_constant(self) -> f64 {
+        match self {
+            Self::US => 15.0,
+            Self::Metric => 127.0,
+        }
+    }
+}
+
+/// Back and ahead station values where stationing jumps, e.g. "12+34.00 = 20+00.00 AHD".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StationEquation {
+    pub back: f64,
+    pub ahead: f64,
+}
+
+pub fn coerce_station_equation(string: &str) -> Result<StationEquation, Error> {
+    let sides = string.split('=').collect::<Vec<&str>>();
+    if sides.len() != 2 {
+        return Err(Error::ParseStationEquation);
+    }
+
+    Ok(StationEquation {
+        back: coerce_station_value(strip_equation_suffix(sides[0]))?,
+        ahead: coerce_station_value(strip_equation_suffix(sides[1]))?,
+    })
+}
+
+fn strip_equation_suffix(side: &str) -> &str {
+    let side = side.trim();
+    for suffix in ["AHEAD", "AHD", "BACK", "BK"] {
+        let split = side.len().saturating_sub(suffix.len());
+        if let (Some(value), Some(tail)) = (side.get(..split), side.get(split..)) {
+            if tail.eq_ignore_ascii_case(suffix) {
+                return value;
+            }
+        }
+    }
+    side
+}
+
+/// A design speed in mph (US) or km/h (Metric).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DesignSpeed {
+    pub value: f64,
+    pub unit: UnitSystem,
+}
+
+impl fmt::Display for DesignSpeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit.speed_label())
+    }
+}
+
+impl DesignSpeed {
+    pub fn mph(value: f64) -> Self {
+        DesignSpeed {
+            value,
+            unit: UnitSystem::US,
+        }
+    }
+
+    pub fn kph(value: f64) -> Self {
+        DesignSpeed {
+            value,
+            unit: UnitSystem::Metric,
+        }
+    }
+
+    pub fn to_mph(&self) -> f64 {
+        match self.unit {
+            UnitSystem::US => self.value,
+            UnitSystem::Metric => self.value * MPH_PER_KPH,
+        }
+    }
+
+    pub fn to_kph(&self) -> f64 {
+        match self.unit {
+            UnitSystem::US => self.value / MPH_PER_KPH,
+            UnitSystem::Metric => self.value,
+        }
+    }
+
+    pub fn to_unit(&self, unit: UnitSystem) -> Self {
+        match unit {
+            UnitSystem::US => DesignSpeed::mph(self.to_mph()),
+            UnitSystem::Metric => DesignSpeed::kph(self.to_kph()),
+        }
+    }
+
+    /// Key into a design speed table, only when the speed lands exactly on a whole number.
+    pub fn table_key(&self, unit: UnitSystem) -> Option<i32> {
+        let value = self.to_unit(unit).value;
+        match (value - value.round()).abs() < 1e-9 {
+            true => Some(value.round() as i32),
+            false => None,
+        }
+    }
+}
+
+/// Parses `10284+50` notation, or a plain number with no `+` (`1028450`) as the raw station value in feet.
+/// A `+` always means station notation, so `10284+50` and `1028450` are the same station.
 pub fn coerce_station_value(string: &str) -> Result<f64, Error> {
+    coerce_station_with_base(string, 100.0)
+}
+
+/// Parses metric `km+m` notation, so `12+345.678` is 12345.678 m. A plain number is the raw value in m.
+pub fn coerce_metric_station(string: &str) -> Result<f64, Error> {
+    coerce_station_with_base(string, 1000.0)
+}
+
+/// Station notation whose value before the `+` counts `base` units.
+fn coerce_station_with_base(string: &str, base: f64) -> Result<f64, Error> {
+    if string.contains('=') {
+        return Err(Error::StationEquation);
+    }
+    if string.trim().is_empty() {
+        return Err(Error::NoValue);
+    }
+    if !string.contains('+') {
+        return string
+            .trim()
+            .parse::<f64>()
+            .map_err(|x| Error::ParseNonFloat);
+    }
     let mut station_vec = vec![];
     for slice in string.split_terminator('+') {
         station_vec.push(
@@ -197,8 +501,7 @@ pub fn coerce_station_value(string: &str) -> Result<f64, Error> {
     if station_vec[1].is_sign_negative() {
         return Err(Error::DifferentSign);
     }
-
-    Ok(station_vec[0] * 100.0 + station_vec[1] * station_vec[0].signum())
+    Ok(station_vec[0] * base + station_vec[1] * station_vec[0].signum())
 }
 
 pub fn coerce_elevation(string: &str) -> Result<f64, Error> {
@@ -214,26 +517,145 @@ pub fn coerce_elevation(string: &str) -> Result<f64, Error> {
     }
 }
 
+/// Parses a length in decimal feet from a decimal ("12.5"), feet-inches ("12'6\"") or a mixed fraction ("12 1/2").
 pub fn coerce_length(string: &str) -> Result<f64, Error> {
-    if string.is_empty() {
+    let trimmed = string.trim();
+    if trimmed.is_empty() {
         Err(Error::NoLenValue)
+    } else if trimmed.contains(['\'', '\"']) {
+        coerce_feet_inches(trimmed)
+    } else if trimmed.contains('/') {
+        coerce_mixed_fraction(trimmed)
     } else {
-        let slice = string
+        let slice = trimmed.parse::<f64>().map_err(|x| Error::ParseLength)?;
+
+        Ok(slice)
+    }
+}
+
+fn coerce_feet_inches(string: &str) -> Result<f64, Error> {
+    let (feet, inches) = match string.split_once('\'') {
+        Some((feet, inches)) => (feet, inches.trim()),
+        None => ("0", string),
+    };
+    let feet = feet
+        .trim()
+        .parse::<f64>()
+        .map_err(|x| Error::AmbiguousLength)?;
+    let inches = match inches.strip_suffix('\"') {
+        Some(inches) if !inches.contains(['\'', '\"']) => inches
             .trim()
             .parse::<f64>()
-            .map_err(|x| Error::ParseLength)?;
+            .map_err(|x| Error::AmbiguousLength)?,
+        None if inches.is_empty() => 0.0,
+        _ => return Err(Error::AmbiguousLength),
+    };
+    if feet < 0.0 || !(0.0..12.0).contains(&inches) {
+        return Err(Error::AmbiguousLength);
+    }
 
-        Ok(slice)
+    Ok(feet + inches / 12.0)
+}
+
+fn coerce_mixed_fraction(string: &str) -> Result<f64, Error> {
+    let (whole, fraction) = match string.rsplit_once(' ') {
+        Some((whole, fraction)) => (whole.trim(), fraction),
+        None => ("0", string),
+    };
+    let whole = whole.parse::<u32>().map_err(|x| Error::AmbiguousLength)?;
+    let (numerator, denominator) = fraction.split_once('/').ok_or(Error::AmbiguousLength)?;
+    let numerator = numerator
+        .parse::<u32>()
+        .map_err(|x| Error::AmbiguousLength)?;
+    let denominator = denominator
+        .parse::<u32>()
+        .map_err(|x| Error::AmbiguousLength)?;
+    if denominator == 0 || numerator >= denominator {
+        return Err(Error::AmbiguousLength);
     }
+
+    Ok(whole as f64 + numerator as f64 / denominator as f64)
+}
+
+/// Whether the degree of curve is subtended by a standard arc or a standard chord.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CurveDefinition {
+    /// Highway practice, `R = L / D`.
+    #[default]
+    Arc,
+    /// Railway practice, `R = (L/2) / sin(D/2)`.
+    Chord,
 }
 
-pub fn coerce_speed(string: &str) -> Result<i32, Error> {
-    let slice = string
+/// Standard arc or chord length a degree of curve is measured on, 100 ft or 20 m.
+fn degree_basis(unit: UnitSystem) -> f64 {
+    match unit {
+        UnitSystem::US => 100.,
+        UnitSystem::Metric => 20.,
+    }
+}
+
+pub fn degree_to_radius(degree: Angle, definition: CurveDefinition, unit: UnitSystem) -> f64 {
+    let basis = degree_basis(unit);
+    match definition {
+        CurveDefinition::Arc => basis / degree.radians,
+        CurveDefinition::Chord => basis / 2. / (degree.radians / 2.).sin(),
+    }
+}
+
+pub fn radius_to_degree(radius: f64, definition: CurveDefinition, unit: UnitSystem) -> Angle {
+    let basis = degree_basis(unit);
+    Angle {
+        radians: match definition {
+            CurveDefinition::Arc => basis / radius,
+            CurveDefinition::Chord => 2. * (basis / 2. / radius).asin(),
+        },
+    }
+}
+
+/// Parses a radius length, or a degree of curve prefixed with `D=` ("D=2d30'") on a 100 ft (US) or 20 m (Metric) arc.
+pub fn coerce_radius(string: &str, unit_system: UnitSystem) -> Result<f64> {
+    let trimmed = string.trim();
+    let Some(degree_of_curve) = trimmed
+        .strip_prefix("D=")
+        .or_else(|| trimmed.strip_prefix("d="))
+    else {
+        return Ok(coerce_length(trimmed)?);
+    };
+    let degree_of_curve = Angle::from(degree_of_curve.trim())?;
+    if degree_of_curve.radians <= 0.0 {
+        return Err(Error::ZeroDegreeOfCurve.into());
+    }
+
+    Ok(degree_to_radius(
+        degree_of_curve,
+        CurveDefinition::Arc,
+        unit_system,
+    ))
+}
+
+/// Parses a design speed. A bare number is read in `unit_system`; "mph", "km/h" and "kph" override it.
+pub fn coerce_speed(string: &str, unit_system: UnitSystem) -> Result<DesignSpeed, Error> {
+    let trimmed = string.trim();
+    let (number, unit) = trimmed.split_at(
+        trimmed
+            .find(|c: char| c.is_alphabetic())
+            .unwrap_or(trimmed.len()),
+    );
+    let slice = number
         .trim()
-        .parse::<i32>()
+        .parse::<f64>()
         .map_err(|x| Error::ParseSpeed)?;
 
-    Ok(slice)
+    match unit.trim().to_lowercase().as_str() {
+        "" => Ok(DesignSpeed {
+            value: slice,
+            unit: unit_system,
+        }),
+        "mph" => Ok(DesignSpeed::mph(slice)),
+        "km/h" | "kph" => Ok(DesignSpeed::kph(slice)),
+        _ => Err(Error::UnknownSpeedUnit),
+    }
 }
 
 pub fn coerce_grade(string: &str) -> Result<f64, Error> {
@@ -280,6 +702,12 @@ pub enum Error {
     /// Station can only have one "+" sign.
     #[error("Station can only have one \"+\" sign.")]
     ExcessiveValues,
+    /// Station is a station equation.
+    #[error("Station is a station equation, only one station is expected.")]
+    StationEquation,
+    /// Station equation is misconfigured.
+    #[error("Station equation requires one \"=\" between the back and ahead stations.")]
+    ParseStationEquation,
     /// Only one negative sign is required.
     #[error("Only one negative sign is required.")]
     DifferentSign,
@@ -292,12 +720,21 @@ pub enum Error {
     /// Length is misconfigured with unexpected symbol.
     #[error("Length is misconfigured with unexpected symbol.")]
     ParseLength,
+    /// Length mixes feet, inches or fractions in an unreadable way.
+    #[error("Length is ambiguous, use a decimal (12.5), feet-inches (12'6\") or a mixed fraction (12 1/2).")]
+    AmbiguousLength,
     /// Length is required.
     #[error("Length is required.")]
     NoLenValue,
+    /// Degree of curve must be greater than zero.
+    #[error("Degree of curve must be greater than zero.")]
+    ZeroDegreeOfCurve,
     /// Speed is misconfigured with unexpected symbol.
     #[error("Speed is misconfigured with unexpected symbol.")]
     ParseSpeed,
+    /// Speed unit isn't recognized.
+    #[error("Speed unit isn't recognized, use \"mph\" or \"km/h\".")]
+    UnknownSpeedUnit,
     /// Grade is required.
     #[error("Grade is required.")]
     NoGradeValue,
@@ -307,14 +744,22 @@ pub enum Error {
     /// Angle is misconfigured with unexpected symbol.
     #[error("Angle is misconfigured with unexpected symbol.")]
     ParseAngle,
-    /// Angle is too large.
-    #[error("Angle is too large.")]
-    OversizedAngle,
+    /// Angle is 180° or more, a reflex angle can't be a roadway curve.
+    #[error("Angle {degrees}° is too large, curve angles must be less than 180°.")]
+    OversizedAngle { degrees: f64 },
+    /// Bearing is misconfigured.
+    #[error("Bearing requires N or S, an angle up to 90°, then E or W.")]
+    ParseBearing,
 }
 
 #[cfg(test)]
 mod data_tests {
-    use crate::datatypes::Angle;
+    use crate::datatypes::{
+        coerce_length, coerce_metric_station, coerce_speed, coerce_station_equation,
+        coerce_station_value, degree_to_radius, radius_to_degree, Angle, AngleFormat,
+        CurveDefinition, DesignSpeed, Error, MetricStationFmt, Station, StationEquation,
+        StationFmt, UnitSystem,
+    };
     use anyhow::Result;
 
     #[test]
@@ -354,4 +799,200 @@ mod data_tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn speed_units() {
+        assert_eq!(
+            coerce_speed("65 mph", UnitSystem::US).unwrap(),
+            DesignSpeed::mph(65.)
+        );
+        assert_eq!(
+            coerce_speed("100 km/h", UnitSystem::US).unwrap(),
+            DesignSpeed::kph(100.)
+        );
+        assert_eq!(
+            coerce_speed("100kph", UnitSystem::US).unwrap(),
+            DesignSpeed::kph(100.)
+        );
+        assert_eq!(
+            coerce_speed("65", UnitSystem::US).unwrap(),
+            DesignSpeed::mph(65.)
+        );
+        assert!(
+            (coerce_speed("100 km/h", UnitSystem::US).unwrap().to_mph() - 62.1371).abs() < 1e-9
+        );
+        assert_eq!(
+            coerce_speed("100", UnitSystem::Metric).unwrap(),
+            DesignSpeed::kph(100.)
+        );
+        assert!(coerce_speed("65 knots", UnitSystem::US).is_err());
+    }
+
+    #[test]
+    fn fractional_speed() {
+        let speed = coerce_speed("62.5", UnitSystem::US).unwrap();
+        assert_eq!(speed.value, 62.5);
+        assert_eq!(speed.table_key(UnitSystem::US), None);
+        assert_eq!(DesignSpeed::mph(65.).table_key(UnitSystem::US), Some(65));
+    }
+
+    #[test]
+    fn station_digits() {
+        let station = Station {
+            value: 1028450.,
+            ..Default::default()
+        };
+        assert_eq!(format!("{}", station), "STA: 10284+50.00");
+        assert_eq!(format!("{}", StationFmt(station, 2)), "STA: 10284+50.00");
+        assert_eq!(format!("{}", StationFmt(station, 3)), "STA: 1028+450.00");
+
+        let station = Station {
+            value: 1005.,
+            ..Default::default()
+        };
+        assert_eq!(format!("{}", station), "STA: 10+05.00");
+    }
+
+    #[test]
+    fn station_without_plus() -> Result<()> {
+        assert_eq!(coerce_station_value("1028450")?, 1028450.);
+        assert_eq!(
+            coerce_station_value("1028450")?,
+            coerce_station_value("10284+50")?
+        );
+        assert_eq!(coerce_station_value(" 10284.50 ")?, 10284.5);
+        assert_eq!(
+            coerce_station_value("-1005")?,
+            coerce_station_value("-10+05")?
+        );
+        assert!(matches!(coerce_station_value(""), Err(Error::NoValue)));
+        assert!(matches!(
+            coerce_station_value("10284+50+00"),
+            Err(Error::ExcessiveValues)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn radius_degree_round_trip() -> Result<()> {
+        let one_degree = Angle::from("1d")?;
+        let cases = [
+            (CurveDefinition::Arc, UnitSystem::US, 5729.578),
+            (CurveDefinition::Chord, UnitSystem::US, 5729.651),
+            (CurveDefinition::Arc, UnitSystem::Metric, 1145.916),
+            (CurveDefinition::Chord, UnitSystem::Metric, 1145.930),
+        ];
+        for (definition, unit, radius) in cases {
+            assert!((degree_to_radius(one_degree, definition, unit) - radius).abs() < 1e-3);
+            let degree = radius_to_degree(radius, definition, unit);
+            assert!((degree.to_decimal_degrees() - 1.).abs() < 1e-3);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn metric_km_stations() -> Result<()> {
+        let value = coerce_metric_station("12+345.678")?;
+        assert!((value - 12345.678).abs() < 1e-9);
+        let station = Station {
+            value,
+            ..Default::default()
+        };
+        assert_eq!(format!("{}", MetricStationFmt(station)), "STA: 12+345.678");
+        assert_eq!(coerce_metric_station("0+045.5")?, 45.5);
+        assert_eq!(coerce_metric_station("12345.678")?, 12345.678);
+        // US notation is never read as km+m, whatever the offset digits.
+        assert_eq!(coerce_station_value("10+050")?, 1050.);
+        assert_eq!(coerce_station_value("10+05")?, 1005.);
+        Ok(())
+    }
+
+    #[test]
+    fn station_equation() {
+        let raw = "12+34.00 = 20+00.00 AHD";
+        assert_eq!(
+            coerce_station_equation(raw).unwrap(),
+            StationEquation {
+                back: 1234.,
+                ahead: 2000.,
+            }
+        );
+        assert!(matches!(
+            coerce_station_value(raw),
+            Err(Error::StationEquation)
+        ));
+    }
+
+    #[test]
+    fn decimal_and_dms_agree() -> Result<()> {
+        let dms = Angle::from("63d15\'34\"")?;
+        let decimal = Angle::from(" 63.259444444444 ")?;
+
+        assert!((dms.radians - decimal.radians).abs() < 1e-12);
+        assert_eq!(Angle::from("10d")?, Angle::from("10")?);
+        Ok(())
+    }
+
+    #[test]
+    fn bearing_quadrants() -> Result<()> {
+        let cases = [
+            (45.5, "N 45°30'00\" E"),
+            (134.5, "S 45°30'00\" E"),
+            (225.5, "S 45°30'00\" W"),
+            (314.5, "N 45°30'00\" W"),
+        ];
+        for (azimuth, bearing) in cases {
+            let angle = Angle {
+                radians: azimuth * std::f64::consts::PI / 180.,
+            };
+            assert_eq!(angle.to_bearing_string(), bearing);
+            assert!((Angle::from_bearing(bearing)?.to_decimal_degrees() - azimuth).abs() < 1e-9);
+        }
+        assert!(Angle::from_bearing("N 95°00'00\" E").is_err());
+        assert!(Angle::from_bearing("E 45°00'00\" N").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn feet_inches_and_fractions() -> Result<()> {
+        assert_eq!(coerce_length("12'6\"")?, 12.5);
+        assert_eq!(coerce_length("12' 6\"")?, 12.5);
+        assert_eq!(coerce_length("12'")?, 12.0);
+        assert_eq!(coerce_length("6\"")?, 0.5);
+        assert_eq!(coerce_length("12 1/2")?, 12.5);
+        assert_eq!(coerce_length("3/4")?, 0.75);
+        assert_eq!(coerce_length(" 12.5 ")?, 12.5);
+        for ambiguous in ["12'6", "12'13\"", "12 3/2", "12 1/0", "1/2'", "12 1/2 3"] {
+            assert!(matches!(
+                coerce_length(ambiguous),
+                Err(Error::AmbiguousLength)
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn right_angle_in_gon() -> Result<()> {
+        let right = Angle::from("90d")?;
+        assert_eq!(right.format(AngleFormat::Gon), "100.0000 gon");
+        assert_eq!(right.format(AngleFormat::Dms), right.to_dms());
+        Ok(())
+    }
+
+    #[test]
+    fn sum_deflection_series() -> Result<()> {
+        let series = [
+            Angle::from("10d20'30\"")?,
+            Angle::from("5d45'45\"")?,
+            Angle::from("2d53'50\"")?,
+        ];
+        let total = Angle::sum(&series);
+
+        // 17d118'125" = 19d0'05"
+        assert!((total.to_decimal_degrees() - (19. + 5. / 3600.)).abs() < 1e-9);
+        assert_eq!(total.to_dms(), "19d0'5.00\"");
+        assert!((series[0].scaled(2.).to_decimal_degrees() - 20.683333).abs() < 1e-6);
+        assert_eq!(Angle::sum(&[]).radians, 0.0);
+        Ok(())
+    }
 }
@@ -0,0 +1,34 @@
+use crate::datatypes::Angle;
+use crate::horizontal::calculate::HorizontalCurve;
+use crate::horizontal::coordinates::Coordinate;
+use crate::horizontal::TurnDirection;
+
+/// LandXML point text, northing then easting.
+fn landxml_point(coord: Coordinate) -> String {
+    format!("{:.4} {:.4}", coord.1, coord.0)
+}
+
+impl HorizontalCurve {
+    /// LandXML `<Curve>` element for the curve starting at `pc_coord` on the `incoming` bearing.
+    pub fn to_landxml(&self, pc_coord: Coordinate, incoming: Angle) -> String {
+        let rot = match self.turn {
+            TurnDirection::Right => "cw",
+            TurnDirection::Left => "ccw",
+        };
+        let end = self
+            .coordinate_at(self.stations.pt.value, pc_coord, incoming)
+            .unwrap_or(pc_coord);
+
+        format!(
+            "<Curve rot=\"{}\" radius=\"{:.4}\" length=\"{:.4}\" staStart=\"{:.4}\" delta=\"{:.8}\">\n  <Start>{}</Start>\n  <Center>{}</Center>\n  <End>{}</End>\n</Curve>\n",
+            rot,
+            self.dimensions.radius,
+            self.dimensions.curve_length,
+            self.stations.pc.value,
+            self.dimensions.curve_angle.to_decimal_degrees(),
+            landxml_point(pc_coord),
+            landxml_point(self.radius_point(pc_coord, incoming)),
+            landxml_point(end)
+        )
+    }
+}
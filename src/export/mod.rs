@@ -3,6 +3,7 @@ use genpdf::fonts::{FontData, FontFamily};
 use native_dialog::FileDialog;
 
 pub mod horizontal;
+pub mod landxml;
 pub mod vertical;
 
 #[derive(Debug, Clone, Copy, Default)]
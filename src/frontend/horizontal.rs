@@ -13,7 +13,7 @@ pub fn horizontal_header_group<'a>() -> Column<'a, Message> {
 }
 
 impl HorizontalData {
-    pub fn horizontal_input_group(&self) -> Column<Message> {
+    pub fn horizontal_input_group(&self) -> Column<'_, Message> {
         let mut column = column![]
             .spacing(10)
             .width(Length::FillPortion(2))
@@ -39,6 +39,8 @@ impl HorizontalData {
             match self.input_build_method {
                 HorizontalBuildDefinition::RadiusCurveAngle => self.row_radius_curve_angle(),
                 HorizontalBuildDefinition::RadiusTangent => self.row_radius_tangent(),
+                HorizontalBuildDefinition::RadiusLength => self.row_radius_length(),
+                HorizontalBuildDefinition::TangentCurveAngle => self.row_tangent_curve_angle(),
             }
             .spacing(H_S),
         ]
@@ -64,6 +66,25 @@ impl HorizontalData {
         ]
     }
 
+    fn row_radius_length(&self) -> Row<'_, Message> {
+        row![
+            text("Radius:"),
+            text_input("(100)", &self.input_radius).on_input(Message::RadiusModify),
+            text("Length:"),
+            text_input("(123)", &self.input_length).on_input(Message::LengthModify)
+        ]
+    }
+
+    fn row_tangent_curve_angle(&self) -> Row<'_, Message> {
+        row![
+            text("Tangent:"),
+            text_input("(123)", &self.input_tangent).on_input(Message::TangentModify),
+            text("Curve Angle:"),
+            text_input("(60d13\'42\")", &self.input_curve_angle)
+                .on_input(Message::CurveAngleModify)
+        ]
+    }
+
     fn optional_block(&self) -> Column<'_, Message> {
         column![
             subtitle("Additional Details"),
@@ -92,7 +113,7 @@ impl HorizontalData {
         .spacing(H_S)
     }
 
-    pub fn horizontal_output_group(&self) -> Column<Message> {
+    pub fn horizontal_output_group(&self) -> Column<'_, Message> {
         let h_s = 5;
         let mut column = column![]
             .spacing(10)
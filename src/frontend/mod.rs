@@ -260,7 +260,7 @@ impl Application for CurveSolver {
         }
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let body = match self {
             CurveSolver::Vertical(vertical_data) => {
                 row![
@@ -16,7 +16,7 @@ pub fn vertical_header_group<'a>() -> Column<'a, Message> {
 }
 
 impl VerticalData {
-    pub fn vertical_input_group(&self) -> Column<Message> {
+    pub fn vertical_input_group(&self) -> Column<'_, Message> {
         let mut column = column![]
             .spacing(10)
             .width(Length::FillPortion(2))
@@ -103,7 +103,7 @@ impl VerticalData {
         }
     }
 
-    pub fn vertical_output_group(&self) -> Column<Message> {
+    pub fn vertical_output_group(&self) -> Column<'_, Message> {
         let h_s = 5;
         let mut column = column![]
             .spacing(10)
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::horizontal::*;
+
+impl HorizontalData {
+    /// Hash of every input that feeds `to_horizontal_curve`.
+    pub fn input_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (
+            self.input_station_method,
+            self.input_build_method,
+            self.turn,
+            &self.input_station,
+            &self.input_length,
+            &self.input_radius,
+            &self.input_curve_angle,
+            &self.input_tangent,
+        )
+            .hash(&mut hasher);
+        (
+            self.input_sight_type,
+            &self.input_design_speed,
+            &self.input_m,
+            self.input_design_standard,
+            self.input_unit_system,
+            self.sustained_downgrade,
+        )
+            .hash(&mut hasher);
+
+        hasher.finish()
+    }
+}
+
+/// Remembers the last curve built so redraws with unchanged inputs skip the calculation.
+#[derive(Debug, Clone, Default)]
+pub struct CurveCache {
+    last: Option<(u64, HorizontalCurve)>,
+    hits: usize,
+}
+
+impl CurveCache {
+    /// The cached curve when the inputs hash the same as last time, otherwise a fresh build.
+    /// Failed builds aren't cached.
+    pub fn to_horizontal_curve(
+        &mut self,
+        horizontal_data: &HorizontalData,
+    ) -> Result<HorizontalCurve> {
+        let key = horizontal_data.input_hash();
+        if let Some((cached_key, curve)) = self.last {
+            if cached_key == key {
+                self.hits += 1;
+                return Ok(curve);
+            }
+        }
+        let curve = horizontal_data.to_horizontal_curve()?;
+        self.last = Some((key, curve));
+
+        Ok(curve)
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+}
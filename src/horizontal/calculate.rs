@@ -1,16 +1,21 @@
 use anyhow::Result;
+use std::f64::consts::PI;
 
 use crate::horizontal::*;
-use crate::tables::get_min_sight;
+use crate::sight::{stopping_sight_distance, SsdParameters};
+use crate::tables::{get_min_sight, get_min_turning_radius, SightError};
 
-#[derive(Debug, Clone, Copy)]
+/// Centerline points per sight distance when measuring the setback on a spiraled curve.
+const SETBACK_SAMPLES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct HorizontalStations {
     pub pc: Station,
     pub pi: Station,
     pub pt: Station,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct HorizontalDimensions {
     pub radius: f64,
     pub curve_length: f64,
@@ -18,40 +23,485 @@ pub struct HorizontalDimensions {
     pub long_chord: f64,
     pub middle_ordinate: f64,
     pub external: f64,
-    pub curve_length_100: Angle, // Da
+    /// Degree of curve on a 100-ft arc (Da). US only, see `degree_of_curve` for metric curves.
+    pub curve_length_100: Angle,
     pub curve_angle: Angle,
-    pub design_speed: i32,
+    pub design_speed: DesignSpeed,
     pub sight_distance: f64,
+    pub unit_system: UnitSystem,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct HorizontalCurve {
     pub dimensions: HorizontalDimensions,
     pub stations: HorizontalStations,
+    pub turn: TurnDirection,
+}
+
+/// Outcome of a plan-review check, comparing the curve against a required value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveCheck {
+    pub passed: bool,
+    pub required: f64,
+    pub actual: f64,
+    pub message: String,
+}
+
+pub(crate) const M_PER_FT: f64 = 0.3048;
+/// Relative tolerance `is_valid` allows between stored and recomputed dimensions.
+const CONSISTENCY_TOLERANCE: f64 = 1e-6;
+
+impl HorizontalDimensions {
+    /// Curve geometry from radius and central angle, with no design speed or sight distance.
+    pub fn from_radius_curve_angle(radius: f64, curve_angle: Angle) -> Self {
+        HorizontalDimensions {
+            radius,
+            curve_length: radius * curve_angle.to_decimal_degrees() * PI / 180.0,
+            tangent: radius * (curve_angle.radians / 2.0).tan(),
+            long_chord: 2.0 * radius * (curve_angle.radians / 2.0).sin(),
+            middle_ordinate: radius * (1.0 - (curve_angle.radians / 2.0).cos()),
+            external: radius * (1.0 / (curve_angle.radians / 2.0).cos() - 1.0),
+            curve_length_100: radius_to_degree(radius, CurveDefinition::Arc, UnitSystem::US),
+            curve_angle,
+            design_speed: DesignSpeed::default(),
+            sight_distance: 0.0,
+            unit_system: UnitSystem::default(),
+        }
+    }
+
+    /// Central angle subtended by a 20 m arc, the usual metric degree of curve basis.
+    pub fn degree_per_20m(&self) -> Angle {
+        radius_to_degree(self.radius, CurveDefinition::Arc, UnitSystem::Metric)
+    }
+
+    /// Degree of curve on the arc basis of the unit system: 100 ft (US) or 20 m (Metric).
+    pub fn degree_of_curve(&self) -> Angle {
+        match self.unit_system {
+            UnitSystem::US => self.curve_length_100,
+            UnitSystem::Metric => self.degree_per_20m(),
+        }
+    }
 }
 
 impl HorizontalCurve {
+    /// Places the dimensions on stationing starting at `pc_station`.
+    pub fn from_dimensions(dimensions: HorizontalDimensions, pc_station: f64) -> Self {
+        HorizontalCurve {
+            dimensions,
+            stations: HorizontalStations {
+                pc: Station {
+                    value: pc_station,
+                    ..Default::default()
+                },
+                pi: Station {
+                    value: pc_station + dimensions.tangent,
+                    ..Default::default()
+                },
+                pt: Station {
+                    value: pc_station + dimensions.curve_length,
+                    ..Default::default()
+                },
+            },
+            turn: TurnDirection::default(),
+        }
+    }
+
+    /// Curve from a signed central angle, positive turning right and negative turning left.
+    pub fn from_signed_curve_angle(radius: f64, curve_angle: Angle, pc_station: f64) -> Self {
+        HorizontalCurve {
+            turn: TurnDirection::from_signed(curve_angle),
+            ..HorizontalCurve::from_dimensions(
+                HorizontalDimensions::from_radius_curve_angle(
+                    radius,
+                    Angle {
+                        radians: curve_angle.radians.abs(),
+                    },
+                ),
+                pc_station,
+            )
+        }
+    }
+
+    /// Central angle carrying the turn as its sign, negative for left turns.
+    pub fn signed_curve_angle(&self) -> Angle {
+        Angle {
+            radians: self.turn.sign() * self.dimensions.curve_angle.radians,
+        }
+    }
+
+    /// Moves the PC, PI and PT by `delta`, rebasing local stationing onto the project's.
+    pub fn shift_stations(&mut self, delta: f64) {
+        self.stations.pc.value += delta;
+        self.stations.pi.value += delta;
+        self.stations.pt.value += delta;
+    }
+
+    /// Curve whose external distance is `external`, `Δ = 2 * acos(R / (R + E))`.
+    pub fn from_radius_external(radius: f64, external: f64, pc_station: f64) -> Result<Self> {
+        if radius <= 0.0 {
+            return Err(Error::NonPositiveDimension { name: "Radius" }.into());
+        }
+        if external <= 0.0 {
+            return Err(Error::NonPositiveDimension { name: "External" }.into());
+        }
+        let curve_angle = Angle {
+            radians: 2. * (radius / (radius + external)).acos(),
+        };
+
+        Ok(HorizontalCurve::from_dimensions(
+            HorizontalDimensions::from_radius_curve_angle(radius, curve_angle),
+            pc_station,
+        ))
+    }
+
+    /// Curve whose middle ordinate is `m`, `Δ = 2 * acos(1 - M / R)` for `0 < M < R`.
+    pub fn from_radius_middle_ordinate(radius: f64, m: f64, pc_station: f64) -> Result<Self> {
+        if radius <= 0.0 {
+            return Err(Error::NonPositiveDimension { name: "Radius" }.into());
+        }
+        if m <= 0.0 {
+            return Err(Error::NonPositiveDimension {
+                name: "Middle ordinate",
+            }
+            .into());
+        }
+        if m >= radius {
+            return Err(Error::OversizedMiddleOrdinate.into());
+        }
+        let curve_angle = Angle {
+            radians: 2. * (1. - m / radius).acos(),
+        };
+
+        Ok(HorizontalCurve::from_dimensions(
+            HorizontalDimensions::from_radius_curve_angle(radius, curve_angle),
+            pc_station,
+        ))
+    }
+
+    /// Distance measured back along the tangent from the PI to lay out the PC.
+    pub fn pc_from_pi_distance(&self) -> f64 {
+        self.dimensions.tangent
+    }
+
+    /// Distance measured ahead along the tangent from the PI to lay out the PT.
+    pub fn pt_from_pi_distance(&self) -> f64 {
+        self.dimensions.tangent
+    }
+
+    /// Pavement area as centerline curve length times `width`. Exact when the pavement is centered
+    /// on the centerline, since the inside edge loses what the outside edge gains.
+    pub fn pavement_area(&self, width: f64) -> f64 {
+        self.dimensions.curve_length * width
+    }
+
+    /// Station `arc_from_pc` along the curve on a constant decimal `grade` rising from `pc_elevation`.
+    pub fn point_with_grade(&self, arc_from_pc: f64, grade: f64, pc_elevation: f64) -> Station {
+        Station {
+            value: self.stations.pc.value + arc_from_pc,
+            elevation: Some(pc_elevation + grade * arc_from_pc),
+            ..Default::default()
+        }
+    }
+
+    /// Signed offsets from the centerline, right positive, of the (inside, outside) traveled way edges
+    /// when `widening` is added entirely to the inside of the turn.
+    pub fn widened_offsets(&self, width: f64, widening: f64) -> (f64, f64) {
+        let inside = self.turn.sign();
+
+        (inside * (width / 2. + widening), -inside * width / 2.)
+    }
+
+    /// Whether the curve passes between the PI and an obstruction `obstruction_offset` from the PI
+    /// along the bisector toward the curve center.
+    pub fn clears_pi_obstruction(&self, obstruction_offset: f64) -> bool {
+        self.dimensions.external < obstruction_offset
+    }
+
+    /// Largest radius whose external distance reaches the obstruction, `R = E / (sec(Δ/2) - 1)`.
+    pub fn radius_to_clear(&self, obstruction_offset: f64) -> f64 {
+        obstruction_offset / (1. / (self.dimensions.curve_angle.radians / 2.).cos() - 1.)
+    }
+
+    /// Clearance needed inside the curve to see `sight_distance` along it, the HSO middle ordinate
+    /// `M = R * (1 - cos(28.65 * S / R))`.
+    pub fn required_setback(&self, sight_distance: f64) -> f64 {
+        let radius = self.dimensions.radius;
+
+        radius * (1. - (28.65 * sight_distance / radius * PI / 180.).cos())
+    }
+
+    /// Setback for the analytic stopping sight distance at `design_speed` braking on decimal `grade`,
+    /// negative for downgrades.
+    pub fn required_setback_on_grade(&self, design_speed: DesignSpeed, grade: f64) -> f64 {
+        self.required_setback(stopping_sight_distance(
+            design_speed,
+            grade,
+            SsdParameters::default(),
+        ))
+    }
+
+    /// Setback needed to see `sight_distance` when an equal clothoid spiral of `spiral_length` eases into
+    /// and out of the curve, keeping the total deflection Δ so the circular arc shortens to
+    /// `R * (Δ - Ls / R)`. Found as the widest gap between every `sight_distance` long stretch of the
+    /// spiral-curve-spiral centerline and its sight line. Matches `required_setback` without spirals
+    /// while the sight distance stays on the arc.
+    pub fn required_setback_spiraled(
+        &self,
+        sight_distance: f64,
+        spiral_length: f64,
+    ) -> Result<f64> {
+        let radius = self.dimensions.radius;
+        let arc_length = radius * self.dimensions.curve_angle.radians - spiral_length;
+        if spiral_length < 0.0 || arc_length < 0.0 {
+            return Err(Error::SpiralTooLong { spiral_length }.into());
+        }
+        if sight_distance <= 0.0 {
+            return Err(Error::NonPositiveDimension {
+                name: "Sight distance",
+            }
+            .into());
+        }
+
+        // Tangent a sight distance long on each side so the sight line can run off the curve.
+        let step = sight_distance / SETBACK_SAMPLES as f64;
+        let curvature = |s: f64| {
+            let s = s - sight_distance;
+            let total = 2. * spiral_length + arc_length;
+            if s <= 0.0 || s >= total {
+                0.0
+            } else if s < spiral_length {
+                s / (spiral_length * radius)
+            } else if s > spiral_length + arc_length {
+                (total - s) / (spiral_length * radius)
+            } else {
+                1. / radius
+            }
+        };
+        let count = ((2. * (sight_distance + spiral_length) + arc_length) / step).ceil() as usize;
+        let mut points = vec![(0.0, 0.0)];
+        let (mut heading, mut position) = (0.0, (0.0, 0.0));
+        for i in 0..count {
+            let s = i as f64 * step;
+            let mid_heading = heading + curvature(s + step / 4.) * step / 2.;
+            heading +=
+                (curvature(s) + 4. * curvature(s + step / 2.) + curvature(s + step)) / 6. * step;
+            position = (
+                position.0 + step * mid_heading.sin(),
+                position.1 + step * mid_heading.cos(),
+            );
+            points.push(position);
+        }
+
+        Ok(points
+            .windows(SETBACK_SAMPLES + 1)
+            .map(|window| {
+                let (start, end) = (window[0], window[SETBACK_SAMPLES]);
+                let (d_x, d_y) = (end.0 - start.0, end.1 - start.1);
+                let chord = d_x.hypot(d_y);
+                window
+                    .iter()
+                    .map(|point| {
+                        ((point.0 - start.0) * d_y - (point.1 - start.1) * d_x).abs() / chord
+                    })
+                    .fold(0.0, f64::max)
+            })
+            .fold(0.0, f64::max))
+    }
+
+    /// Portion of a full circle the arc sweeps, `Δ / 360°`, between 0 and 1 for a valid curve.
+    pub fn circle_fraction(&self) -> f64 {
+        self.dimensions.curve_angle.radians / (2. * PI)
+    }
+
+    /// `circle_fraction` as a percent.
+    pub fn arc_percent(&self) -> f64 {
+        self.circle_fraction() * 100.
+    }
+
+    /// Mid-ordinate of a `chord_length` chord, `v = R - sqrt(R^2 - (c/2)^2)`.
+    pub fn versine(&self, chord_length: f64) -> f64 {
+        let radius = self.dimensions.radius;
+
+        radius - (radius.powi(2) - (chord_length / 2.).powi(2)).sqrt()
+    }
+
+    /// Track gauge approximation of the versine, `v = c^2 / (8R)`, close for chords short against the radius.
+    pub fn versine_approx(&self, chord_length: f64) -> f64 {
+        chord_length.powi(2) / (8. * self.dimensions.radius)
+    }
+
+    /// Whether the radius is at least the design vehicle's minimum turning radius.
+    pub fn accommodates_vehicle(&self, design_vehicle: DesignVehicle) -> bool {
+        let min_radius = match self.dimensions.unit_system {
+            UnitSystem::US => get_min_turning_radius(design_vehicle),
+            UnitSystem::Metric => get_min_turning_radius(design_vehicle) * M_PER_FT,
+        };
+
+        self.dimensions.radius >= min_radius
+    }
+
+    /// AASHTO minimum length to avoid the appearance of a kink, `L = 100 * (30 - Δ)` ft for
+    /// central angles under 30°. Larger angles have no minimum.
+    pub fn check_minimum_length_for_appearance(&self) -> CurveCheck {
+        let delta = self.dimensions.curve_angle.to_decimal_degrees();
+        let required = match self.dimensions.unit_system {
+            UnitSystem::US => 100. * (30. - delta),
+            UnitSystem::Metric => 100. * (30. - delta) * M_PER_FT,
+        }
+        .max(0.0);
+        let actual = self.dimensions.curve_length;
+        let passed = actual >= required;
+        let message = match passed {
+            true => "Curve length is long enough to avoid a kinked appearance.".to_string(),
+            false => format!(
+                "Curve length {:.2} is less than the {:.2} {} minimum for a {:.2}° deflection.",
+                actual,
+                required,
+                self.dimensions.unit_system.length_label(),
+                delta
+            ),
+        };
+
+        CurveCheck {
+            passed,
+            required,
+            actual,
+            message,
+        }
+    }
+
+    /// Checks the stored dimensions and stations agree with the radius and central angle, listing every
+    /// inconsistency found. PI before PT isn't required, since the tangent outruns the arc once Δ
+    /// passes about 133.6°.
+    pub fn is_valid(&self) -> Result<(), Vec<String>> {
+        let HorizontalDimensions {
+            radius,
+            curve_angle,
+            ..
+        } = self.dimensions;
+        let half_angle = curve_angle.radians / 2.;
+        let (pc, pi, pt) = (
+            self.stations.pc.value,
+            self.stations.pi.value,
+            self.stations.pt.value,
+        );
+        let checks = [
+            (
+                "Tangent",
+                self.dimensions.tangent,
+                radius * half_angle.tan(),
+            ),
+            (
+                "Middle ordinate",
+                self.dimensions.middle_ordinate,
+                radius * (1. - half_angle.cos()),
+            ),
+            (
+                "External",
+                self.dimensions.external,
+                radius * (1. / half_angle.cos() - 1.),
+            ),
+            (
+                "Curve length",
+                self.dimensions.curve_length,
+                radius * curve_angle.radians,
+            ),
+            (
+                "Long chord",
+                self.dimensions.long_chord,
+                2. * radius * half_angle.sin(),
+            ),
+            ("PC to PI", pi - pc, self.dimensions.tangent),
+            ("PC to PT", pt - pc, self.dimensions.curve_length),
+        ];
+
+        let mut errors = vec![];
+        if !(pc < pi && pc < pt) {
+            errors.push(format!(
+                "Stations out of order: PC {:.4}, PI {:.4}, PT {:.4}.",
+                pc, pi, pt
+            ));
+        }
+        for (name, actual, expected) in checks {
+            if (actual - expected).abs() > CONSISTENCY_TOLERANCE * expected.abs().max(1.) {
+                errors.push(format!(
+                    "{} is {:.4}, expected {:.4}.",
+                    name, actual, expected
+                ));
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    /// Compares the sight distance with the tabulated minimum times `adjustment`, both in the
+    /// curve's units.
     pub fn is_compliant(
         &self,
         design_standard: DesignStandard,
         sight_type: SightType,
         adjustment: f64,
     ) -> Result<(bool, f64), Error> {
-        let min_sight = get_min_sight(self.dimensions.design_speed, design_standard, sight_type);
-        match min_sight {
-            None => Err(Error::DesignSpeedLUTError),
-            Some(w) => Ok((
-                (self.dimensions.sight_distance >= w * adjustment),
-                w * adjustment,
-            )),
-        }
+        // The sight distance tables are in mph and ft.
+        let min_sight = get_min_sight(
+            self.dimensions.design_speed.to_unit(UnitSystem::US),
+            design_standard,
+            sight_type,
+        )? * adjustment;
+        let min_sight = match self.dimensions.unit_system {
+            UnitSystem::US => min_sight,
+            UnitSystem::Metric => min_sight * M_PER_FT,
+        };
+        Ok((self.dimensions.sight_distance >= min_sight, min_sight))
     }
 }
 
+/// Radii for a degree of curve under the chord definition (100 ft chord, `R = 50 / sin(D/2)`) and
+/// the arc definition (100 ft arc, `R = 100 / D`), as `(chord, arc)`.
+pub fn chord_vs_arc_radius(degree: Angle) -> (f64, f64) {
+    (
+        degree_to_radius(degree, CurveDefinition::Chord, UnitSystem::US),
+        degree_to_radius(degree, CurveDefinition::Arc, UnitSystem::US),
+    )
+}
+
+/// How much longer the chord definition radius is than the arc definition radius, in percent.
+pub fn chord_arc_radius_difference(degree: Angle) -> f64 {
+    let (chord, arc) = chord_vs_arc_radius(degree);
+
+    (chord - arc) / arc * 100.
+}
+
 /// Horizontal Calculate Errors.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Design speed isn't specified in the manual.
     #[error("Design speed isn't specified in the manual.")]
     DesignSpeedLUTError,
+    /// Curve angle is zero, so the curve is a tangent.
+    #[error("Curve angle is zero, so the curve is a tangent.")]
+    ZeroCurveAngle,
+    /// Dimension must be greater than zero.
+    #[error("{name} must be greater than zero.")]
+    NonPositiveDimension { name: &'static str },
+    /// No curve tangent to both bearings passes through the point.
+    #[error("Point doesn't lie on any curve tangent to both bearings.")]
+    PointOffCurve,
+    /// Middle ordinate must be less than the radius.
+    #[error("Middle ordinate must be less than the radius.")]
+    OversizedMiddleOrdinate,
+    /// Curve length must be a positive, finite number before stationing.
+    #[error("Curve length {curve_length} must be positive and finite.")]
+    InvalidCurveLength { curve_length: f64 },
+    /// Spirals must leave a non-negative circular arc.
+    #[error("Spiral length {spiral_length} leaves no circular arc for the curve angle.")]
+    SpiralTooLong { spiral_length: f64 },
+    /// Sight distance lookup failed.
+    #[error(transparent)]
+    Sight(#[from] SightError),
+    /// Compound curve split ratio must fall strictly between 0 and 1.
+    #[error("Split ratio {split_ratio} must be between 0 and 1.")]
+    SplitRatioOutOfRange { split_ratio: f64 },
 }
@@ -0,0 +1,77 @@
+use anyhow::Result;
+
+use crate::horizontal::*;
+
+/// Two curves turning the same way that meet at a common PCC, the first curve stationed from 0+00.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompoundCurve {
+    pub first: HorizontalCurve,
+    pub second: HorizontalCurve,
+    /// Distance between the PIs of the two curves, `T1 + T2` of the sub-curves.
+    pub common_tangent: f64,
+    /// Distance from the PC to the overall PI.
+    pub tangent_in: f64,
+    /// Distance from the overall PI to the PT.
+    pub tangent_out: f64,
+}
+
+impl CompoundCurve {
+    /// Splits `total_delta` into `split_ratio * Δ` on the `r1` curve and the rest on the `r2` curve.
+    /// The overall tangents follow from the triangle the common tangent closes with the main tangents.
+    pub fn solve(r1: f64, r2: f64, total_delta: Angle, split_ratio: f64) -> Result<CompoundCurve> {
+        if r1 <= 0.0 || r2 <= 0.0 {
+            return Err(calculate::Error::NonPositiveDimension { name: "Radius" }.into());
+        }
+        if total_delta.radians.abs() < f64::EPSILON {
+            return Err(calculate::Error::ZeroCurveAngle.into());
+        }
+        if total_delta.radians < 0.0 {
+            return Err(calculate::Error::NonPositiveDimension {
+                name: "Total curve angle",
+            }
+            .into());
+        }
+        if total_delta.radians >= PI {
+            return Err(crate::datatypes::Error::OversizedAngle {
+                degrees: total_delta.to_decimal_degrees(),
+            }
+            .into());
+        }
+        if split_ratio <= 0.0 || split_ratio >= 1.0 {
+            return Err(calculate::Error::SplitRatioOutOfRange { split_ratio }.into());
+        }
+        let delta_1 = Angle {
+            radians: total_delta.radians * split_ratio,
+        };
+        let delta_2 = Angle {
+            radians: total_delta.radians - delta_1.radians,
+        };
+        let first = HorizontalCurve::from_dimensions(
+            HorizontalDimensions::from_radius_curve_angle(r1, delta_1),
+            0.0,
+        );
+        let second = HorizontalCurve::from_dimensions(
+            HorizontalDimensions::from_radius_curve_angle(r2, delta_2),
+            first.stations.pt.value,
+        );
+        let common_tangent = first.dimensions.tangent + second.dimensions.tangent;
+        let sin_delta = total_delta.radians.sin();
+
+        Ok(CompoundCurve {
+            first,
+            second,
+            common_tangent,
+            tangent_in: first.dimensions.tangent
+                + common_tangent * delta_2.radians.sin() / sin_delta,
+            tangent_out: second.dimensions.tangent
+                + common_tangent * delta_1.radians.sin() / sin_delta,
+        })
+    }
+
+    pub fn total_delta(&self) -> Angle {
+        Angle {
+            radians: self.first.dimensions.curve_angle.radians
+                + self.second.dimensions.curve_angle.radians,
+        }
+    }
+}
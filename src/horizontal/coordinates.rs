@@ -0,0 +1,297 @@
+use anyhow::Result;
+use std::f64::consts::PI;
+
+use crate::horizontal::*;
+
+/// Coordinates are (easting, northing). Bearings are azimuths measured clockwise from north.
+pub type Coordinate = (f64, f64);
+
+/// Places local coordinates, PC at the origin and back tangent due north, into a project grid by
+/// rotating clockwise through `rotation` and then translating by `translation`. The coordinate
+/// methods take the grid placement from `placement`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridTransform {
+    pub translation: Coordinate,
+    pub rotation: Angle,
+}
+
+impl GridTransform {
+    pub fn apply(&self, local: Coordinate) -> Coordinate {
+        let (sin, cos) = self.rotation.radians.sin_cos();
+
+        (
+            self.translation.0 + local.0 * cos + local.1 * sin,
+            self.translation.1 - local.0 * sin + local.1 * cos,
+        )
+    }
+
+    /// PC coordinate and back tangent bearing on the grid, as taken by `coordinate_at`,
+    /// `radius_point`, `bounding_box` and `to_landxml`.
+    pub fn placement(&self) -> (Coordinate, Angle) {
+        (self.apply((0., 0.)), self.rotation)
+    }
+}
+
+/// Points sampled along the arc for its extents.
+const BOUNDING_SAMPLES: usize = 64;
+
+impl HorizontalCurve {
+    pub fn outgoing_bearing(&self, incoming: Angle) -> Angle {
+        Angle {
+            radians: normalize_radians(
+                incoming.radians + self.turn.sign() * self.dimensions.curve_angle.radians,
+            ),
+        }
+    }
+
+    /// Back tangent bearing, the inverse of `outgoing_bearing`.
+    pub fn incoming_bearing(&self, outgoing: Angle) -> Angle {
+        Angle {
+            radians: normalize_radians(
+                outgoing.radians - self.turn.sign() * self.dimensions.curve_angle.radians,
+            ),
+        }
+    }
+
+    pub fn pi_coordinate(&self, pc_coord: Coordinate, incoming: Angle) -> Coordinate {
+        offset(pc_coord, incoming.radians, self.dimensions.tangent)
+    }
+
+    /// PC coordinate from the coordinate of whichever point anchors the stationing. `bearing` is the
+    /// tangent through the anchor: the back tangent for PC and PI, the ahead tangent for PT.
+    pub fn pc_coordinate(
+        &self,
+        anchor: HorizontalStationDefinition,
+        anchor_coord: Coordinate,
+        bearing: Angle,
+    ) -> Coordinate {
+        let tangent = self.dimensions.tangent;
+        match anchor {
+            HorizontalStationDefinition::PC => anchor_coord,
+            HorizontalStationDefinition::PI => offset(anchor_coord, bearing.radians + PI, tangent),
+            HorizontalStationDefinition::PT => {
+                let pi = offset(anchor_coord, bearing.radians + PI, tangent);
+                offset(pi, self.incoming_bearing(bearing).radians + PI, tangent)
+            }
+        }
+    }
+
+    /// Bearing sighted from the PI back to the PC, the reverse of the back tangent.
+    pub fn pi_to_pc_bearing(&self, incoming: Angle) -> Angle {
+        Angle {
+            radians: normalize_radians(incoming.radians + PI),
+        }
+    }
+
+    /// Bearing sighted from the PI ahead to the PT, the ahead tangent.
+    pub fn pi_to_pt_bearing(&self, incoming: Angle) -> Angle {
+        self.outgoing_bearing(incoming)
+    }
+
+    /// Bearing of the PC to PT line, halfway between the incoming and outgoing tangents.
+    pub fn long_chord_bearing(&self, incoming: Angle) -> Angle {
+        Angle {
+            radians: normalize_radians(
+                incoming.radians + self.turn.sign() * self.dimensions.curve_angle.radians / 2.,
+            ),
+        }
+    }
+
+    /// Coordinate of the curve center, a radius off the PC to the inside of the turn.
+    pub fn radius_point(&self, pc_coord: Coordinate, incoming: Angle) -> Coordinate {
+        offset(
+            pc_coord,
+            incoming.radians + self.turn.sign() * PI / 2.,
+            self.dimensions.radius,
+        )
+    }
+
+    /// Bearing from the curve center to the point at `station`, or `None` off the curve.
+    pub fn radial_bearing(&self, station: f64, incoming: Angle) -> Option<Angle> {
+        if station < self.stations.pc.value || station > self.stations.pt.value {
+            return None;
+        }
+        let delta_radians = (station - self.stations.pc.value) / self.dimensions.radius;
+
+        Some(Angle {
+            radians: normalize_radians(
+                incoming.radians + self.turn.sign() * (delta_radians - PI / 2.),
+            ),
+        })
+    }
+
+    /// Station and coordinate of the PC, every even `station_interval` and the PT.
+    pub fn coordinate_points(
+        &self,
+        pc_coord: Coordinate,
+        incoming: Angle,
+        station_interval: f64,
+    ) -> Vec<(Station, Coordinate)> {
+        self.stakeout_stations(station_interval)
+            .into_iter()
+            .filter_map(|value| {
+                let coordinate = self.coordinate_at(value, pc_coord, incoming)?;
+                Some((
+                    Station {
+                        value,
+                        ..Default::default()
+                    },
+                    coordinate,
+                ))
+            })
+            .collect()
+    }
+
+    /// Curve tangent to both bearings at `pi` that passes through `point`, stationed with the PC at 0+00.
+    /// Solves `|P - C| = R` with the center `C` a distance `R / cos(Δ/2)` along the bisector from the PI.
+    pub fn fit_through_point(
+        pi: Coordinate,
+        incoming: Angle,
+        outgoing: Angle,
+        point: Coordinate,
+    ) -> Result<HorizontalCurve> {
+        let (curve_angle, turn) = central_angle_from_bearings(incoming, outgoing);
+        if curve_angle.radians.abs() < f64::EPSILON {
+            return Err(calculate::Error::ZeroCurveAngle.into());
+        }
+        let half_angle = curve_angle.radians / 2.;
+        let bisector = incoming.radians + turn.sign() * (PI + curve_angle.radians) / 2.;
+        let (u_x, u_y) = (bisector.sin(), bisector.cos());
+        let (d_x, d_y) = (point.0 - pi.0, point.1 - pi.1);
+        let k = 1. / half_angle.cos();
+        let d_u = d_x * u_x + d_y * u_y;
+        let d_d = d_x.powi(2) + d_y.powi(2);
+
+        // R^2 (k^2 - 1) - 2 k (d.u) R + |d|^2 = 0
+        let a = k.powi(2) - 1.;
+        let discriminant = (k * d_u).powi(2) - a * d_d;
+        if discriminant < 0.0 {
+            return Err(calculate::Error::PointOffCurve.into());
+        }
+        let radius = [
+            (k * d_u - discriminant.sqrt()) / a,
+            (k * d_u + discriminant.sqrt()) / a,
+        ]
+        .into_iter()
+        .find(|radius| {
+            // The point must fall between the PC and PT radials, not on the far side of the circle.
+            let (c_x, c_y) = (pi.0 + radius * k * u_x, pi.1 + radius * k * u_y);
+            *radius > 0.0
+                && -((point.0 - c_x) * u_x + (point.1 - c_y) * u_y)
+                    >= radius * half_angle.cos() - 1e-9 * radius
+        })
+        .ok_or(calculate::Error::PointOffCurve)?;
+
+        Ok(HorizontalCurve {
+            turn,
+            ..HorizontalCurve::from_dimensions(
+                HorizontalDimensions::from_radius_curve_angle(radius, curve_angle),
+                0.0,
+            )
+        })
+    }
+
+    /// Extents `(min_x, min_y, max_x, max_y)` of the arc from points sampled between the PC and PT.
+    pub fn bounding_box(&self, pc_coord: Coordinate, incoming: Angle) -> (f64, f64, f64, f64) {
+        let (pc, length) = (self.stations.pc.value, self.dimensions.curve_length);
+
+        (0..=BOUNDING_SAMPLES)
+            .filter_map(|i| {
+                let station = pc + length * i as f64 / BOUNDING_SAMPLES as f64;
+                self.coordinate_at(station.min(self.stations.pt.value), pc_coord, incoming)
+            })
+            .fold(
+                (
+                    f64::INFINITY,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                    f64::NEG_INFINITY,
+                ),
+                |(min_x, min_y, max_x, max_y), (x, y)| {
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                },
+            )
+    }
+
+    /// Curve of `radius` at `pi`, its back tangent through `back_point` and ahead tangent through
+    /// `ahead_point`, stationed with the PC at 0+00.
+    pub fn from_pi_tangent_points(
+        pi: Coordinate,
+        back_point: Coordinate,
+        ahead_point: Coordinate,
+        radius: f64,
+    ) -> Result<HorizontalCurve> {
+        if radius <= 0.0 {
+            return Err(calculate::Error::NonPositiveDimension { name: "Radius" }.into());
+        }
+        if back_point == pi || ahead_point == pi {
+            return Err(calculate::Error::NonPositiveDimension {
+                name: "Distance from the PI to a tangent point",
+            }
+            .into());
+        }
+        let (curve_angle, turn) =
+            central_angle_from_bearings(bearing(back_point, pi), bearing(pi, ahead_point));
+        if curve_angle.radians.abs() < f64::EPSILON {
+            return Err(calculate::Error::ZeroCurveAngle.into());
+        }
+
+        Ok(HorizontalCurve {
+            turn,
+            ..HorizontalCurve::from_dimensions(
+                HorizontalDimensions::from_radius_curve_angle(radius, curve_angle),
+                0.0,
+            )
+        })
+    }
+
+    pub fn coordinate_at(
+        &self,
+        station: f64,
+        pc_coord: Coordinate,
+        incoming: Angle,
+    ) -> Option<Coordinate> {
+        if station < self.stations.pc.value || station > self.stations.pt.value {
+            return None;
+        }
+        let arc = station - self.stations.pc.value;
+        let delta_radians = arc / self.dimensions.radius;
+        let chord = 2. * self.dimensions.radius * (delta_radians / 2.).sin();
+        let chord_bearing = incoming.radians + self.turn.sign() * delta_radians / 2.;
+
+        Some(offset(pc_coord, chord_bearing, chord))
+    }
+}
+
+/// Deflection between two tangent bearings and the direction the curve turns to get there.
+pub fn central_angle_from_bearings(incoming: Angle, outgoing: Angle) -> (Angle, TurnDirection) {
+    let clockwise = normalize_radians(outgoing.radians - incoming.radians);
+    if clockwise <= PI {
+        (Angle { radians: clockwise }, TurnDirection::Right)
+    } else {
+        (
+            Angle {
+                radians: 2. * PI - clockwise,
+            },
+            TurnDirection::Left,
+        )
+    }
+}
+
+/// Azimuth of the line from `from` to `to`.
+pub fn bearing(from: Coordinate, to: Coordinate) -> Angle {
+    Angle {
+        radians: normalize_radians((to.0 - from.0).atan2(to.1 - from.1)),
+    }
+}
+
+pub fn offset(coord: Coordinate, bearing_radians: f64, distance: f64) -> Coordinate {
+    (
+        coord.0 + distance * bearing_radians.sin(),
+        coord.1 + distance * bearing_radians.cos(),
+    )
+}
+
+pub fn normalize_radians(radians: f64) -> f64 {
+    radians.rem_euclid(2. * PI)
+}
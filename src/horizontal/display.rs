@@ -4,14 +4,27 @@ use crate::horizontal::*;
 
 impl fmt::Display for HorizontalDimensions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Radius: {:.2}", self.radius)?;
-        writeln!(f, "Curve Length: {:.2}", self.curve_length)?;
-        writeln!(f, "Tangent: {:.2}", self.tangent)?;
-        writeln!(f, "Long Chord: {:.2}", self.long_chord)?;
-        writeln!(f, "Middle Ordinate: {:.2}", self.middle_ordinate)?;
-        writeln!(f, "External: {:.2}", self.external.abs())?;
+        let unit = self.unit_system.length_label();
+        writeln!(f, "Radius: {:.2} {}", self.radius, unit)?;
+        writeln!(f, "Curve Length: {:.2} {}", self.curve_length, unit)?;
+        writeln!(f, "Tangent: {:.2} {}", self.tangent, unit)?;
+        writeln!(f, "Long Chord: {:.2} {}", self.long_chord, unit)?;
+        writeln!(f, "Middle Ordinate: {:.2} {}", self.middle_ordinate, unit)?;
+        writeln!(f, "External: {:.2} {}", self.external.abs(), unit)?;
         writeln!(f, "Angle: {}", self.curve_angle)?;
-        writeln!(f, "Angle/100: {}", self.curve_length_100)?;
+        match self.unit_system {
+            UnitSystem::US => writeln!(f, "Angle/100: {}", self.curve_length_100)?,
+            UnitSystem::Metric => writeln!(f, "Angle/20m: {}", self.degree_per_20m())?,
+        }
+        if self.design_speed.value > 0.0 {
+            let design_speed = self.design_speed.to_unit(self.unit_system);
+            writeln!(
+                f,
+                "Design Speed: {} {}",
+                (design_speed.value * 100.0).round() / 100.0,
+                self.unit_system.speed_label()
+            )?;
+        }
         Ok(())
     }
 }
@@ -32,3 +45,51 @@ impl fmt::Display for HorizontalCurve {
         Ok(())
     }
 }
+
+/// One curve as a row of a plan set curve data table.
+#[derive(Debug, Clone, Copy)]
+pub struct CurveSummaryRow {
+    pub pc: Station,
+    pub pi: Station,
+    pub pt: Station,
+    pub radius: f64,
+    pub curve_angle: Angle,
+    pub curve_length: f64,
+    pub tangent: f64,
+}
+
+impl CurveSummaryRow {
+    pub fn header() -> &'static str {
+        "PC | PI | PT | Radius | Delta | Length | Tangent"
+    }
+}
+
+impl fmt::Display for CurveSummaryRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} | {} | {} | {:.2} | {} | {:.2} | {:.2}",
+            self.pc,
+            self.pi,
+            self.pt,
+            self.radius,
+            self.curve_angle.to_dms(),
+            self.curve_length,
+            self.tangent
+        )
+    }
+}
+
+impl HorizontalCurve {
+    pub fn summary_row(&self) -> CurveSummaryRow {
+        CurveSummaryRow {
+            pc: self.stations.pc,
+            pi: self.stations.pi,
+            pt: self.stations.pt,
+            radius: self.dimensions.radius,
+            curve_angle: self.dimensions.curve_angle,
+            curve_length: self.dimensions.curve_length,
+            tangent: self.dimensions.tangent,
+        }
+    }
+}
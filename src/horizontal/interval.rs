@@ -1,3 +1,6 @@
+use std::f64::consts::PI;
+use std::fmt;
+
 use crate::datatypes::Station;
 use crate::horizontal::*;
 
@@ -61,3 +64,248 @@ impl HorizontalCurve {
         curve_detail
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StakeoutRow {
+    pub station: Station,
+    pub arc: f64,
+    pub deflection: Angle,
+    pub chord: f64,
+    pub incremental_chord: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StakeoutTable {
+    pub rows: Vec<StakeoutRow>,
+    pub angle_format: AngleFormat,
+}
+
+impl fmt::Display for StakeoutTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rows {
+            writeln!(
+                f,
+                "> {} ARC: {:.2} DEFL: {} CHOR: {:.2} INCR: {:.2}",
+                row.station,
+                row.arc,
+                row.deflection.format(self.angle_format),
+                row.chord,
+                row.incremental_chord
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl StakeoutTable {
+    /// GitHub flavored Markdown table, each column padded to its widest cell.
+    pub fn to_markdown(&self) -> String {
+        let header =
+            ["Station", "Arc", "Deflection", "Chord", "Incremental Chord"].map(String::from);
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                [
+                    row.station
+                        .to_string()
+                        .trim_start_matches("STA: ")
+                        .to_string(),
+                    format!("{:.2}", row.arc),
+                    row.deflection.format(self.angle_format),
+                    format!("{:.2}", row.chord),
+                    format!("{:.2}", row.incremental_chord),
+                ]
+            })
+            .collect::<Vec<[String; 5]>>();
+        let mut widths = header.clone().map(|cell| cell.len().max(3));
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let line = |cells: &[String; 5]| {
+            let padded = cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell))
+                .collect::<Vec<String>>();
+            format!("| {} |\n", padded.join(" | "))
+        };
+        let separator = widths.map(|width| "-".repeat(width + 2));
+
+        let mut markdown = line(&header);
+        markdown += &format!("|{}|\n", separator.join("|"));
+        for row in &rows {
+            markdown += &line(row);
+        }
+
+        markdown
+    }
+}
+
+/// Where interior stakeout points fall between the PC and PT.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StationSnapping {
+    /// Even multiples of the interval, leaving the PC and PT as the only odd stations.
+    #[default]
+    EvenStations,
+    /// Exactly one interval apart starting from the PC.
+    FromPc,
+}
+
+impl StationSnapping {
+    pub fn next(self) -> Self {
+        match self {
+            Self::EvenStations => Self::FromPc,
+            Self::FromPc => Self::EvenStations,
+        }
+    }
+}
+
+impl HorizontalCurve {
+    /// PC, every even multiple of `station_interval` on the curve, then PT.
+    pub fn stakeout_stations(&self, station_interval: f64) -> Vec<f64> {
+        self.stakeout_stations_with(station_interval, StationSnapping::default())
+    }
+
+    /// PC, interior points every `station_interval` placed by `snapping`, then PT.
+    pub fn stakeout_stations_with(
+        &self,
+        station_interval: f64,
+        snapping: StationSnapping,
+    ) -> Vec<f64> {
+        let pc = self.stations.pc.value;
+        let pt = self.stations.pt.value;
+        let mut values = vec![pc];
+
+        if station_interval > 0.0 {
+            let mut running = match snapping {
+                StationSnapping::EvenStations => {
+                    ((pc / station_interval).floor() + 1.0) * station_interval
+                }
+                StationSnapping::FromPc => pc + station_interval,
+            };
+            while running < pt {
+                values.push(running);
+                running += station_interval;
+            }
+        }
+        values.push(pt);
+
+        values
+    }
+
+    /// Evenly spaced stations from PC to PT where no chord strays more than `max_error` from the arc.
+    /// A non-positive `max_error` can't be met and yields no stations.
+    pub fn sample_by_sagitta(&self, max_error: f64) -> Vec<Station> {
+        if max_error <= 0.0 {
+            return vec![];
+        }
+        let radius = self.dimensions.radius;
+        let max_arc = 2. * radius * (1. - max_error / radius).max(-1.).acos();
+        let count = (self.dimensions.curve_length / max_arc).ceil().max(1.) as usize;
+        let step = self.dimensions.curve_length / count as f64;
+
+        (0..=count)
+            .map(|i| Station {
+                value: self.stations.pc.value + step * i as f64,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Station whose deflection from the PC is `deflection`, or `None` past the PT.
+    pub fn station_from_deflection(&self, deflection: Angle) -> Option<Station> {
+        if deflection.radians < 0.0
+            || deflection.radians > self.dimensions.curve_angle.radians / 2. + f64::EPSILON
+        {
+            return None;
+        }
+
+        Some(Station {
+            value: self.stations.pc.value + 2. * self.dimensions.radius * deflection.radians,
+            deflection: Some(deflection),
+            ..Default::default()
+        })
+    }
+
+    /// (distance along the PC tangent, perpendicular offset) pairs every `interval` out to the PT's
+    /// tangent distance, `y = R - sqrt(R² - x²)`. The offset is measured toward the curve center.
+    pub fn tangent_offsets(&self, interval: f64) -> Vec<(f64, f64)> {
+        if interval <= 0.0 {
+            return vec![];
+        }
+        let radius = self.dimensions.radius;
+        let x_max = match self.dimensions.curve_angle.radians < PI / 2. {
+            true => radius * self.dimensions.curve_angle.radians.sin(),
+            false => radius,
+        };
+        let y = |x: f64| radius - (radius.powi(2) - x.powi(2)).sqrt();
+
+        let mut offsets = vec![];
+        let mut x = 0.0;
+        while x < x_max {
+            offsets.push((x, y(x)));
+            x += interval;
+        }
+        offsets.push((x_max, y(x_max)));
+
+        offsets
+    }
+
+    /// Chord between each consecutive pair of stakeout stations, PC to PT.
+    pub fn incremental_chords(&self, station_interval: f64) -> Vec<f64> {
+        self.stakeout_table(station_interval)
+            .rows
+            .iter()
+            .skip(1)
+            .map(|row| row.incremental_chord)
+            .collect()
+    }
+
+    pub fn stakeout_table(&self, station_interval: f64) -> StakeoutTable {
+        let radius = self.dimensions.radius;
+        let mut stakeout_table = StakeoutTable::default();
+        let mut previous_arc = 0.0;
+
+        for value in self.stakeout_stations(station_interval) {
+            let arc = value - self.stations.pc.value;
+            let deflection = Angle {
+                radians: arc / (2. * radius),
+            };
+            stakeout_table.rows.push(StakeoutRow {
+                station: Station {
+                    value,
+                    ..Default::default()
+                },
+                arc,
+                deflection,
+                chord: 2. * radius * deflection.radians.sin(),
+                incremental_chord: 2. * radius * ((arc - previous_arc) / (2. * radius)).sin(),
+            });
+            previous_arc = arc;
+        }
+
+        stakeout_table
+    }
+
+    /// `stakeout_table` with each deflection rounded to the nearest `angle_precision_seconds`, the
+    /// least count of the instrument turning it. A least count of zero leaves the deflections exact.
+    pub fn stakeout_table_rounded(
+        &self,
+        station_interval: f64,
+        angle_precision_seconds: u32,
+    ) -> StakeoutTable {
+        let mut stakeout_table = self.stakeout_table(station_interval);
+        if angle_precision_seconds == 0 {
+            return stakeout_table;
+        }
+        let least_count = (angle_precision_seconds as f64 / 3600.).to_radians();
+        for row in &mut stakeout_table.rows {
+            row.deflection.radians = (row.deflection.radians / least_count).round() * least_count;
+        }
+
+        stakeout_table
+    }
+}
@@ -1,16 +1,23 @@
 use anyhow::Result;
 use std::f64::consts::PI;
+use std::fmt;
 
 use crate::datatypes::*;
 use crate::export::*;
 
+pub mod cache;
 pub mod calculate;
+pub mod compound;
+pub mod coordinates;
 pub mod display;
 pub mod interval;
+pub mod report;
+pub mod superelevation;
 
 use self::calculate::*;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HorizontalStationDefinition {
     #[default]
     PI,
@@ -28,28 +35,79 @@ impl HorizontalStationDefinition {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HorizontalBuildDefinition {
     #[default]
     RadiusCurveAngle,
     RadiusTangent,
+    RadiusLength,
+    TangentCurveAngle,
 }
 
 impl HorizontalBuildDefinition {
     pub fn next(self) -> Self {
         match self {
             Self::RadiusCurveAngle => Self::RadiusTangent,
-            Self::RadiusTangent => Self::RadiusCurveAngle,
+            Self::RadiusTangent => Self::RadiusLength,
+            Self::RadiusLength => Self::TangentCurveAngle,
+            Self::TangentCurveAngle => Self::RadiusCurveAngle,
+        }
+    }
+
+    /// `HorizontalData` fields this build method reads.
+    pub fn required_inputs(&self) -> &'static [&'static str] {
+        match self {
+            Self::RadiusCurveAngle => &["input_radius", "input_curve_angle"],
+            Self::RadiusTangent => &["input_radius", "input_tangent"],
+            Self::RadiusLength => &["input_radius", "input_length"],
+            Self::TangentCurveAngle => &["input_tangent", "input_curve_angle"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TurnDirection {
+    #[default]
+    Right,
+    Left,
+}
+
+impl TurnDirection {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Right => Self::Left,
+            Self::Left => Self::Right,
+        }
+    }
+
+    /// Rotation sense of the turn: positive (clockwise) for right turns.
+    pub fn sign(self) -> f64 {
+        match self {
+            Self::Right => 1.0,
+            Self::Left => -1.0,
+        }
+    }
+
+    /// Turn implied by a signed central angle, negative for left turns.
+    pub fn from_signed(curve_angle: Angle) -> Self {
+        match curve_angle.radians < 0.0 {
+            true => Self::Left,
+            false => Self::Right,
         }
     }
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HorizontalData {
     pub input_directory: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub success_flags: [ExportSuccess; 2],
     pub input_station_method: HorizontalStationDefinition,
     pub input_build_method: HorizontalBuildDefinition,
+    pub turn: TurnDirection,
     pub input_station: String,
     pub input_length: String,
     pub input_radius: String,
@@ -60,80 +118,172 @@ pub struct HorizontalData {
     pub input_design_speed: String,
     pub input_m: String,
     pub input_design_standard: DesignStandard,
+    pub input_unit_system: UnitSystem,
     pub sustained_downgrade: bool,
     pub input_pin_station: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub pin: CurveDetail,
 }
 
-impl HorizontalData {
-    fn to_dimensions(&self) -> Result<HorizontalDimensions> {
-        let (
-            radius,
-            curve_angle,
-            curve_length,
-            tangent,
-            external,
-            middle_ordinate,
-            long_chord,
-            curve_length_100,
-            design_speed,
-            sight_distance,
-        );
+/// An input field that failed to coerce.
+#[derive(Debug)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub error: anyhow::Error,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.error)
+    }
+}
 
+impl HorizontalData {
+    /// Coerces every input the build method needs, collecting all failures instead of stopping at the first.
+    pub fn validate_inputs(&self) -> Result<(), Vec<FieldError>> {
+        let mut results: Vec<(&'static str, Result<()>)> = vec![(
+            "input_station",
+            coerce_station_value(&self.input_station)
+                .map(|_| ())
+                .map_err(Into::into),
+        )];
+        if self.input_build_method != HorizontalBuildDefinition::TangentCurveAngle {
+            results.push((
+                "input_radius",
+                coerce_radius(&self.input_radius, self.input_unit_system).map(|_| ()),
+            ));
+        }
         match self.input_build_method {
-            HorizontalBuildDefinition::RadiusCurveAngle => {
-                radius = coerce_length(&self.input_radius)?;
-                curve_angle = Angle::from(self.input_curve_angle.as_str())?;
-
-                curve_length = radius * curve_angle.to_decimal_degrees() * PI / 180.0;
-                tangent = radius * (curve_angle.radians / 2.0).tan();
-                external = radius * (1.0 / (curve_angle.radians / 2.0).cos() - 1.0);
-                middle_ordinate = radius * (1.0 - (curve_angle.radians / 2.0).cos());
-                long_chord = 2.0 * radius * (curve_angle.radians / 2.0).sin();
-                curve_length_100 = Angle {
-                    radians: 5729.6 / radius * PI / 180.0,
-                };
-                let m = coerce_length(&self.input_m).unwrap_or_default();
+            HorizontalBuildDefinition::RadiusCurveAngle => results.push((
+                "input_curve_angle",
+                Angle::from(self.input_curve_angle.as_str()).map(|_| ()),
+            )),
+            HorizontalBuildDefinition::RadiusTangent => results.push((
+                "input_tangent",
+                coerce_length(&self.input_tangent)
+                    .map(|_| ())
+                    .map_err(Into::into),
+            )),
+            HorizontalBuildDefinition::RadiusLength => results.push((
+                "input_length",
+                coerce_length(&self.input_length)
+                    .map(|_| ())
+                    .map_err(Into::into),
+            )),
+            HorizontalBuildDefinition::TangentCurveAngle => results.extend([
+                (
+                    "input_tangent",
+                    coerce_length(&self.input_tangent)
+                        .map(|_| ())
+                        .map_err(Into::into),
+                ),
+                (
+                    "input_curve_angle",
+                    Angle::from(self.input_curve_angle.as_str()).map(|_| ()),
+                ),
+            ]),
+        }
+        if !self.input_design_speed.is_empty() {
+            results.push((
+                "input_design_speed",
+                coerce_speed(&self.input_design_speed, self.input_unit_system)
+                    .map(|_| ())
+                    .map_err(Into::into),
+            ));
+        }
+        if !self.input_m.is_empty() {
+            results.push((
+                "input_m",
+                coerce_length(&self.input_m).map(|_| ()).map_err(Into::into),
+            ));
+        }
 
-                design_speed = coerce_speed(&self.input_design_speed).unwrap_or_default();
-                sight_distance = radius / 28.65 * ((radius - m) / radius).acos() * 180.0 / PI;
-            }
+        let errors = results
+            .into_iter()
+            .filter_map(|(field, result)| result.err().map(|error| FieldError { field, error }))
+            .collect::<Vec<FieldError>>();
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    /// Dimensions and turn direction. A negative curve angle turns left, overriding `turn`.
+    fn to_dimensions(&self) -> Result<(HorizontalDimensions, TurnDirection)> {
+        let (radius, curve_angle) = match self.input_build_method {
+            HorizontalBuildDefinition::RadiusCurveAngle => (
+                coerce_radius(&self.input_radius, self.input_unit_system)?,
+                Angle::from(self.input_curve_angle.as_str())?,
+            ),
             HorizontalBuildDefinition::RadiusTangent => {
-                tangent = coerce_length(&self.input_tangent)?;
-                radius = coerce_length(&self.input_radius)?;
+                let tangent = coerce_length(&self.input_tangent)?;
+                let radius = coerce_radius(&self.input_radius, self.input_unit_system)?;
 
-                curve_angle = Angle {
-                    radians: (tangent / radius).atan() * 2.,
-                };
-                curve_length = radius * curve_angle.to_decimal_degrees() * PI / 180.0;
-                external = radius * (1.0 / (curve_angle.radians / 2.0).cos() - 1.0);
-                middle_ordinate = radius * (1.0 - (curve_angle.radians / 2.0).cos());
-                long_chord = 2.0 * radius * (curve_angle.radians / 2.0).sin();
-                curve_length_100 = Angle {
-                    radians: 5729.6 / radius * PI / 180.0,
-                };
-                let m = coerce_length(&self.input_m).unwrap_or_default();
-
-                design_speed = coerce_speed(&self.input_design_speed).unwrap_or_default();
-                sight_distance = radius / 28.65 * ((radius - m) / radius).acos() * 180.0 / PI;
-            } // _ => return Err(anyhow!("This method hasn't been implimented.")),
-        }
-
-        Ok(HorizontalDimensions {
-            radius,
-            curve_length,
-            tangent,
-            long_chord,
-            middle_ordinate,
-            external,
-            curve_length_100,
-            curve_angle,
-            design_speed,
-            sight_distance,
-        })
+                (
+                    radius,
+                    Angle {
+                        radians: (tangent / radius).atan() * 2.,
+                    },
+                )
+            }
+            HorizontalBuildDefinition::RadiusLength => {
+                let length = coerce_length(&self.input_length)?;
+                let radius = coerce_radius(&self.input_radius, self.input_unit_system)?;
+                let degrees = (length / radius).to_degrees();
+                if degrees >= 180. {
+                    return Err(crate::datatypes::Error::OversizedAngle { degrees }.into());
+                }
+
+                (
+                    radius,
+                    Angle {
+                        radians: length / radius,
+                    },
+                )
+            }
+            // Holds the tangent until the curve angle is known to be nonzero.
+            HorizontalBuildDefinition::TangentCurveAngle => (
+                coerce_length(&self.input_tangent)?,
+                Angle::from(self.input_curve_angle.as_str())?,
+            ), // _ => return Err(anyhow!("This method hasn't been implimented.")),
+        };
+
+        if curve_angle.radians.abs() < f64::EPSILON {
+            return Err(calculate::Error::ZeroCurveAngle.into());
+        }
+        let radius = match self.input_build_method {
+            HorizontalBuildDefinition::TangentCurveAngle => {
+                radius / (curve_angle.radians.abs() / 2.).tan()
+            }
+            _ => radius,
+        };
+        let signed = HorizontalCurve::from_signed_curve_angle(radius, curve_angle, 0.0);
+        let turn = match curve_angle.radians < 0.0 {
+            true => signed.turn,
+            false => self.turn,
+        };
+
+        let m = coerce_length(&self.input_m).unwrap_or_default();
+
+        Ok((
+            HorizontalDimensions {
+                design_speed: coerce_speed(&self.input_design_speed, self.input_unit_system)
+                    .unwrap_or_default(),
+                sight_distance: radius / 28.65 * ((radius - m) / radius).acos() * 180.0 / PI,
+                unit_system: self.input_unit_system,
+                ..signed.dimensions
+            },
+            turn,
+        ))
     }
 
     fn to_stations(&self, dimensions: &HorizontalDimensions) -> Result<HorizontalStations> {
+        if !(dimensions.curve_length.is_finite() && dimensions.curve_length > 0.0) {
+            return Err(calculate::Error::InvalidCurveLength {
+                curve_length: dimensions.curve_length,
+            }
+            .into());
+        }
         let starting_station = Station {
             value: coerce_station_value(&self.input_station)?,
             ..Default::default()
@@ -157,7 +307,7 @@ impl HorizontalData {
             },
         };
 
-        return Ok(stations);
+        Ok(stations)
     }
 
     fn pc_to_pi(&self, sts: Station, dim: &HorizontalDimensions) -> Station {
@@ -183,7 +333,7 @@ impl HorizontalData {
 
     fn pi_to_pt(&self, sts: Station, dim: &HorizontalDimensions) -> Station {
         Station {
-            value: sts.value + dim.tangent,
+            value: sts.value - dim.tangent + dim.curve_length,
             ..Default::default()
         }
     }
@@ -197,18 +347,19 @@ impl HorizontalData {
 
     fn pt_to_pi(&self, sts: Station, dim: &HorizontalDimensions) -> Station {
         Station {
-            value: sts.value - dim.tangent,
+            value: sts.value - dim.curve_length + dim.tangent,
             ..Default::default()
         }
     }
 
     pub fn to_horizontal_curve(&self) -> Result<HorizontalCurve> {
-        let dimensions = self.to_dimensions()?;
+        let (dimensions, turn) = self.to_dimensions()?;
         let stations = self.to_stations(&dimensions)?;
 
         Ok(HorizontalCurve {
             dimensions,
             stations,
+            turn,
         })
     }
 }
@@ -217,6 +368,35 @@ impl HorizontalData {
 mod hori_tests {
     use super::HorizontalData;
 
+    /// R = 818.5, Δ = 63°15'34".
+    const H1_TANGENT: f64 = 504.1300;
+    const H1_CURVE_LENGTH: f64 = 903.6941;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "{} isn't within 1e-3 of {}",
+            actual,
+            expected
+        );
+    }
+
+    /// Inputs for the H1 curve stationed with the PC at 100+00.
+    fn h1_data() -> HorizontalData {
+        HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_build_method: super::HorizontalBuildDefinition::RadiusCurveAngle,
+            input_station: "100+00".to_string(),
+            input_radius: "818.5".to_string(),
+            input_curve_angle: "63d15\'34\"".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn h1_curve() -> super::HorizontalCurve {
+        h1_data().to_horizontal_curve().unwrap()
+    }
+
     #[test]
     fn h1() {
         let horizontal_data = HorizontalData {
@@ -231,10 +411,20 @@ mod hori_tests {
             ..Default::default()
         };
         let hori_angle = horizontal_data.to_horizontal_curve();
-        match hori_angle {
-            Ok(w) => println!("O: {:#?}", w),
-            Err(e) => println!("{}", e),
-        }
+        assert!(hori_angle.is_ok());
+        let curve = hori_angle.unwrap();
+
+        assert_close(curve.dimensions.tangent, H1_TANGENT);
+        assert_close(curve.dimensions.curve_length, H1_CURVE_LENGTH);
+        assert_close(curve.dimensions.long_chord, 858.4881);
+        assert_close(curve.dimensions.external, 142.7957);
+        assert_close(curve.dimensions.middle_ordinate, 121.5841);
+        assert_close(curve.stations.pc.value, 1028450. - H1_TANGENT);
+        assert_close(curve.stations.pi.value, 1028450.);
+        assert_close(
+            curve.stations.pt.value,
+            1028450. - H1_TANGENT + H1_CURVE_LENGTH,
+        );
     }
 
     #[test]
@@ -251,10 +441,1391 @@ mod hori_tests {
             ..Default::default()
         };
         let hori_angle = horizontal_data.to_horizontal_curve();
-        match hori_angle {
-            Ok(w) => println!("O: {:#?}", w),
-            Err(e) => println!("{}", e),
+        assert!(hori_angle.is_ok());
+        let curve = hori_angle.unwrap();
+
+        assert_close(curve.dimensions.tangent, H1_TANGENT);
+        assert_close(curve.dimensions.curve_length, H1_CURVE_LENGTH);
+        assert_close(curve.stations.pc.value, 10000.);
+        assert_close(curve.stations.pi.value, 10000. + H1_TANGENT);
+        assert_close(curve.stations.pt.value, 10000. + H1_CURVE_LENGTH);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn h1_toml_round_trip() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PI,
+            input_build_method: super::HorizontalBuildDefinition::RadiusCurveAngle,
+            input_station: "10284+50".to_string(),
+            input_length: "600".to_string(),
+            input_radius: "818.5".to_string(),
+            input_curve_angle: "63d15\'34\"".to_string(),
+            input_design_speed: "65".to_string(),
+            input_m: "1000".to_string(),
+            ..Default::default()
+        };
+        let serialized = toml::to_string(&horizontal_data).unwrap();
+        let deserialized: HorizontalData = toml::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized.input_station, horizontal_data.input_station);
+        assert_eq!(deserialized.input_radius, horizontal_data.input_radius);
+        assert_eq!(
+            deserialized.input_curve_angle,
+            horizontal_data.input_curve_angle
+        );
+        assert!(matches!(
+            deserialized.input_station_method,
+            super::HorizontalStationDefinition::PI
+        ));
+        assert!(matches!(
+            deserialized.input_build_method,
+            super::HorizontalBuildDefinition::RadiusCurveAngle
+        ));
+        assert_eq!(toml::to_string(&deserialized).unwrap(), serialized);
+    }
+
+    #[test]
+    fn left_right_mirror() {
+        let mut horizontal_data = h1_data();
+        let right = horizontal_data.to_horizontal_curve().unwrap();
+        horizontal_data.turn = super::TurnDirection::Left;
+        let left = horizontal_data.to_horizontal_curve().unwrap();
+        let north = super::Angle { radians: 0. };
+
+        let r_pt = right
+            .coordinate_at(right.stations.pt.value, (0., 0.), north)
+            .unwrap();
+        let l_pt = left
+            .coordinate_at(left.stations.pt.value, (0., 0.), north)
+            .unwrap();
+        assert!(r_pt.0 > 0.);
+        assert!((r_pt.0 + l_pt.0).abs() < 1e-9);
+        assert!((r_pt.1 - l_pt.1).abs() < 1e-9);
+
+        let r_out = right.outgoing_bearing(north).radians;
+        let l_out = left.outgoing_bearing(north).radians;
+        assert!((r_out + l_out - 2. * std::f64::consts::PI).abs() < 1e-9);
+        assert!((r_out - right.dimensions.curve_angle.radians).abs() < 1e-9);
+    }
+
+    #[test]
+    fn stakeout_table_closes_at_pt() {
+        let horizontal_data = HorizontalData {
+            input_station: "10278+21.17".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        let table = curve.stakeout_table(25.0);
+        let last = *table.rows.last().unwrap();
+
+        assert_eq!(last.station.value, curve.stations.pt.value);
+        assert!((last.deflection.radians - curve.dimensions.curve_angle.radians / 2.).abs() < 1e-9);
+        assert!((last.chord - curve.dimensions.long_chord).abs() < 1e-6);
+
+        let incremental_sum: f64 = table.rows.iter().map(|row| row.incremental_chord).sum();
+        assert!(incremental_sum >= curve.dimensions.long_chord);
+        assert!(incremental_sum <= curve.dimensions.curve_length);
+        assert!((incremental_sum - curve.dimensions.curve_length).abs() < 0.1);
+
+        let in_gon = super::interval::StakeoutTable {
+            angle_format: super::AngleFormat::Gon,
+            ..table
+        }
+        .to_string();
+        assert!(in_gon.contains(&format!("DEFL: {:.4} gon", last.deflection.to_gon())));
+    }
+
+    #[test]
+    fn sagitta_sampling_densifies() {
+        let curve = h1_curve();
+        let coarse = curve.sample_by_sagitta(1.0);
+        let fine = curve.sample_by_sagitta(0.1);
+        let finest = curve.sample_by_sagitta(0.01);
+
+        assert!(coarse.len() < fine.len());
+        assert!(fine.len() < finest.len());
+        assert_eq!(finest.first().unwrap().value, curve.stations.pc.value);
+        assert!((finest.last().unwrap().value - curve.stations.pt.value).abs() < 1e-9);
+
+        let step = finest[1].value - finest[0].value;
+        let sagitta = curve.dimensions.radius * (1. - (step / curve.dimensions.radius / 2.).cos());
+        assert!(sagitta <= 0.01);
+    }
+
+    #[test]
+    fn zero_curve_angle() {
+        let horizontal_data = HorizontalData {
+            input_curve_angle: "0d0\'0\"".to_string(),
+            input_tangent: H1_TANGENT.to_string(),
+            ..h1_data()
+        };
+        for build in [
+            super::HorizontalBuildDefinition::RadiusCurveAngle,
+            super::HorizontalBuildDefinition::TangentCurveAngle,
+        ] {
+            let error = HorizontalData {
+                input_build_method: build,
+                ..horizontal_data.clone()
+            }
+            .to_horizontal_curve()
+            .unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<super::calculate::Error>(),
+                Some(super::calculate::Error::ZeroCurveAngle)
+            ));
+        }
+    }
+
+    #[test]
+    fn reflex_curve_angle() {
+        let horizontal_data = HorizontalData {
+            input_curve_angle: "200".to_string(),
+            ..h1_data()
+        };
+        let error = horizontal_data.to_horizontal_curve().unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<crate::datatypes::Error>(),
+            Some(crate::datatypes::Error::OversizedAngle { degrees }) if *degrees == 200.
+        ));
+        assert!(error.to_string().contains("200°"));
+    }
+
+    #[test]
+    fn build_required_inputs() {
+        assert_eq!(
+            super::HorizontalBuildDefinition::RadiusCurveAngle.required_inputs(),
+            ["input_radius", "input_curve_angle"]
+        );
+        assert_eq!(
+            super::HorizontalBuildDefinition::RadiusTangent.required_inputs(),
+            ["input_radius", "input_tangent"]
+        );
+        assert_eq!(
+            super::HorizontalBuildDefinition::RadiusLength.required_inputs(),
+            ["input_radius", "input_length"]
+        );
+        assert_eq!(
+            super::HorizontalBuildDefinition::TangentCurveAngle.required_inputs(),
+            ["input_tangent", "input_curve_angle"]
+        );
+    }
+
+    #[test]
+    fn tangent_curve_angle_recovers_radius() -> anyhow::Result<()> {
+        let known = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PI,
+            input_station: "10284+50".to_string(),
+            ..h1_data()
+        };
+        let from_tangent = HorizontalData {
+            input_build_method: super::HorizontalBuildDefinition::TangentCurveAngle,
+            input_radius: String::new(),
+            input_tangent: H1_TANGENT.to_string(),
+            ..known.clone()
+        };
+        assert!(from_tangent.validate_inputs().is_ok());
+        let curve = from_tangent.to_horizontal_curve()?;
+
+        assert_close(curve.dimensions.radius, 818.5);
+        assert_close(curve.dimensions.curve_length, H1_CURVE_LENGTH);
+        assert_close(
+            curve.stations.pt.value,
+            known.to_horizontal_curve()?.stations.pt.value,
+        );
+        assert_eq!(
+            super::HorizontalBuildDefinition::RadiusLength.next(),
+            super::HorizontalBuildDefinition::TangentCurveAngle
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn validate_collects_all_errors() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_build_method: super::HorizontalBuildDefinition::RadiusCurveAngle,
+            input_station: "100+00".to_string(),
+            input_radius: "818.5ft?".to_string(),
+            input_curve_angle: "63x15".to_string(),
+            ..Default::default()
+        };
+        let errors = horizontal_data.validate_inputs().unwrap_err();
+        let fields = errors.iter().map(|e| e.field).collect::<Vec<&str>>();
+
+        assert_eq!(fields, ["input_radius", "input_curve_angle"]);
+    }
+
+    #[test]
+    fn central_angle_from_tangent_bearings() {
+        use super::coordinates::central_angle_from_bearings;
+        use super::{Angle, TurnDirection};
+
+        let cases = [
+            ("30d", "75d", 45., TurnDirection::Right),
+            ("80d", "20d", 60., TurnDirection::Left),
+            ("350d", "20d", 30., TurnDirection::Right),
+            ("10d", "340d", 30., TurnDirection::Left),
+        ];
+        for (incoming, outgoing, expected, turn) in cases {
+            let (delta, direction) =
+                central_angle_from_bearings(bearing(incoming), bearing(outgoing));
+            assert!((delta.to_decimal_degrees() - expected).abs() < 1e-9);
+            assert_eq!(direction, turn);
+        }
+
+        fn bearing(degrees: &str) -> Angle {
+            Angle {
+                radians: degrees
+                    .trim_end_matches('d')
+                    .parse::<f64>()
+                    .unwrap()
+                    .to_radians(),
+            }
+        }
+    }
+
+    #[test]
+    fn summary_row_matches_header() {
+        let horizontal_data = h1_data();
+        let row = horizontal_data.to_horizontal_curve().unwrap().summary_row();
+
+        assert_eq!(
+            super::display::CurveSummaryRow::header()
+                .split(" | ")
+                .count(),
+            row.to_string().split(" | ").count()
+        );
+    }
+
+    #[test]
+    fn fractional_design_speed() {
+        let horizontal_data = HorizontalData {
+            input_design_speed: "62.5".to_string(),
+            input_m: "1000".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+
+        assert_eq!(curve.dimensions.design_speed.value, 62.5);
+        assert!(curve
+            .is_compliant(
+                super::DesignStandard::CALTRANS,
+                super::SightType::Stopping,
+                1.0
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn metric_sight_compliance() {
+        let mut curve = HorizontalData {
+            input_design_speed: "60 mph".to_string(),
+            input_unit_system: super::UnitSystem::Metric,
+            ..h1_data()
+        }
+        .to_horizontal_curve()
+        .unwrap();
+        curve.dimensions.sight_distance = 180.;
+        let (compliant, min_sight) = curve
+            .is_compliant(
+                super::DesignStandard::CALTRANS,
+                super::SightType::Stopping,
+                1.0,
+            )
+            .unwrap();
+
+        // 60 mph stops in 580 ft, 176.78 m.
+        assert!(compliant);
+        assert_close(min_sight, 176.784);
+    }
+
+    #[test]
+    fn display_unit_labels() {
+        let mut horizontal_data = HorizontalData {
+            input_design_speed: "65".to_string(),
+            ..h1_data()
+        };
+        let us = horizontal_data.to_horizontal_curve().unwrap().to_string();
+        horizontal_data.input_unit_system = super::UnitSystem::Metric;
+        let metric = horizontal_data.to_horizontal_curve().unwrap().to_string();
+
+        assert!(us.contains("Radius: 818.50 ft"));
+        assert!(us.contains("Design Speed: 65 mph"));
+        assert!(metric.contains("Radius: 818.50 m\n"));
+        assert!(metric.contains("Design Speed: 65 km/h"));
+    }
+
+    #[test]
+    fn long_chord_bisects_tangents() -> anyhow::Result<()> {
+        let mut horizontal_data = h1_data();
+        let incoming = super::Angle::from("80d")?;
+        for turn in [super::TurnDirection::Right, super::TurnDirection::Left] {
+            horizontal_data.turn = turn;
+            let curve = horizontal_data.to_horizontal_curve()?;
+            let outgoing = curve.outgoing_bearing(incoming);
+            let chord = curve.long_chord_bearing(incoming);
+
+            assert!((chord.radians - (incoming.radians + outgoing.radians) / 2.).abs() < 1e-9);
+            let pt = curve
+                .coordinate_at(curve.stations.pt.value, (0., 0.), incoming)
+                .unwrap();
+            assert!((pt.0.atan2(pt.1) - chord.radians).abs() < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn from_radius_external_reconstructs() -> anyhow::Result<()> {
+        let curve = h1_curve();
+        let rebuilt = super::HorizontalCurve::from_radius_external(
+            curve.dimensions.radius,
+            curve.dimensions.external,
+            curve.stations.pc.value,
+        )?;
+
+        assert!(
+            (rebuilt.dimensions.curve_angle.radians - curve.dimensions.curve_angle.radians).abs()
+                < 1e-9
+        );
+        assert!((rebuilt.dimensions.tangent - curve.dimensions.tangent).abs() < 1e-6);
+        assert!((rebuilt.dimensions.curve_length - curve.dimensions.curve_length).abs() < 1e-6);
+        assert!((rebuilt.stations.pt.value - curve.stations.pt.value).abs() < 1e-6);
+        assert!(super::HorizontalCurve::from_radius_external(818.5, -1., 10000.).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn from_radius_middle_ordinate_reconstructs() -> anyhow::Result<()> {
+        let curve = h1_curve();
+        let rebuilt = super::HorizontalCurve::from_radius_middle_ordinate(
+            curve.dimensions.radius,
+            curve.dimensions.middle_ordinate,
+            curve.stations.pc.value,
+        )?;
+
+        assert!(
+            (rebuilt.dimensions.curve_angle.radians - curve.dimensions.curve_angle.radians).abs()
+                < 1e-9
+        );
+        assert!((rebuilt.dimensions.long_chord - curve.dimensions.long_chord).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn oversized_middle_ordinate() {
+        let error =
+            super::HorizontalCurve::from_radius_middle_ordinate(818.5, 818.5, 10000.).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<super::calculate::Error>(),
+            Some(super::calculate::Error::OversizedMiddleOrdinate)
+        ));
+    }
+
+    #[test]
+    fn radial_bearings_span_curve_angle() -> anyhow::Result<()> {
+        use super::coordinates::{normalize_radians, offset};
+
+        let mut horizontal_data = h1_data();
+        let incoming = super::Angle::from("20d")?;
+        for turn in [super::TurnDirection::Right, super::TurnDirection::Left] {
+            horizontal_data.turn = turn;
+            let curve = horizontal_data.to_horizontal_curve()?;
+            let at_pc = curve
+                .radial_bearing(curve.stations.pc.value, incoming)
+                .unwrap();
+            let at_pt = curve
+                .radial_bearing(curve.stations.pt.value, incoming)
+                .unwrap();
+            let swept = normalize_radians(turn.sign() * (at_pt.radians - at_pc.radians));
+
+            assert!((swept - curve.dimensions.curve_angle.radians).abs() < 1e-9);
+
+            let center = curve.radius_point((0., 0.), incoming);
+            let pt = offset(center, at_pt.radians, curve.dimensions.radius);
+            let expected = curve
+                .coordinate_at(curve.stations.pt.value, (0., 0.), incoming)
+                .unwrap();
+            assert!((pt.0 - expected.0).abs() < 1e-6 && (pt.1 - expected.1).abs() < 1e-6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fit_through_point_recovers_radius() -> anyhow::Result<()> {
+        use super::coordinates::offset;
+
+        let mut horizontal_data = h1_data();
+        let incoming = super::Angle::from("30d")?;
+        let pc = (1000., 2000.);
+        for turn in [super::TurnDirection::Right, super::TurnDirection::Left] {
+            horizontal_data.turn = turn;
+            let curve = horizontal_data.to_horizontal_curve()?;
+            let pi = offset(pc, incoming.radians, curve.dimensions.tangent);
+            let outgoing = curve.outgoing_bearing(incoming);
+            for fraction in [0.1, 0.5, 0.9] {
+                let station = curve.stations.pc.value + fraction * curve.dimensions.curve_length;
+                let point = curve.coordinate_at(station, pc, incoming).unwrap();
+                let fitted =
+                    super::HorizontalCurve::fit_through_point(pi, incoming, outgoing, point)?;
+
+                assert!((fitted.dimensions.radius - 818.5).abs() < 1e-6);
+                assert_eq!(fitted.turn, turn);
+            }
+        }
+
+        let outgoing = super::Angle::from("90d")?;
+        let behind_pi = offset((0., 0.), incoming.radians, 100.);
+        assert!(
+            super::HorizontalCurve::fit_through_point((0., 0.), incoming, outgoing, behind_pi)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn coordinate_points_close_at_pt() -> anyhow::Result<()> {
+        use super::coordinates::offset;
+
+        let horizontal_data = HorizontalData {
+            input_station: "102+37.43".to_string(),
+            turn: super::TurnDirection::Left,
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve()?;
+        let incoming = super::Angle::from("30d")?;
+        let pc = (1000., 2000.);
+        let points = curve.coordinate_points(pc, incoming, 50.);
+
+        assert_eq!(points.len(), curve.stakeout_stations(50.).len());
+        assert_eq!(points[0], (curve.stations.pc, pc));
+        let (last_station, last_coord) = points[points.len() - 1];
+        assert_eq!(last_station.value, curve.stations.pt.value);
+        let pi = offset(pc, incoming.radians, curve.dimensions.tangent);
+        let pt = offset(
+            pi,
+            curve.outgoing_bearing(incoming).radians,
+            curve.dimensions.tangent,
+        );
+        assert!((last_coord.0 - pt.0).abs() < 1e-6 && (last_coord.1 - pt.1).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn pi_and_pt_anchors_match_pc_anchor() -> anyhow::Result<()> {
+        use super::HorizontalStationDefinition;
+
+        let mut horizontal_data = h1_data();
+        let from_pc = horizontal_data.to_horizontal_curve()?;
+
+        for (method, anchor) in [
+            (HorizontalStationDefinition::PI, from_pc.stations.pi.value),
+            (HorizontalStationDefinition::PT, from_pc.stations.pt.value),
+        ] {
+            horizontal_data.input_station_method = method;
+            horizontal_data.input_station =
+                format!("{}+{}", (anchor / 100.).floor(), anchor % 100.);
+            let anchored = horizontal_data.to_horizontal_curve()?;
+
+            for (a, b) in [
+                (from_pc.stations.pc, anchored.stations.pc),
+                (from_pc.stations.pi, anchored.stations.pi),
+                (from_pc.stations.pt, anchored.stations.pt),
+            ] {
+                assert!((a.value - b.value).abs() < 1e-9, "{:?}", method);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn pt_anchor_matches_pc_anchor() -> anyhow::Result<()> {
+        use super::HorizontalStationDefinition;
+
+        let mut horizontal_data = HorizontalData {
+            turn: super::TurnDirection::Left,
+            ..h1_data()
+        };
+        let from_pc = horizontal_data.to_horizontal_curve()?;
+        let incoming = super::Angle::from("30d")?;
+        let pc = (1000., 2000.);
+        let pt = from_pc
+            .coordinate_at(from_pc.stations.pt.value, pc, incoming)
+            .unwrap();
+        let outgoing = from_pc.outgoing_bearing(incoming);
+
+        horizontal_data.input_station_method = HorizontalStationDefinition::PT;
+        let pt_station = from_pc.stations.pt.value;
+        horizontal_data.input_station =
+            format!("{}+{}", (pt_station / 100.).floor(), pt_station % 100.);
+        let from_pt = horizontal_data.to_horizontal_curve()?;
+
+        for (a, b) in [
+            (from_pc.stations.pc, from_pt.stations.pc),
+            (from_pc.stations.pi, from_pt.stations.pi),
+            (from_pc.stations.pt, from_pt.stations.pt),
+        ] {
+            assert!((a.value - b.value).abs() < 1e-9);
+        }
+        assert!((from_pt.incoming_bearing(outgoing).radians - incoming.radians).abs() < 1e-9);
+        let derived_pc = from_pt.pc_coordinate(HorizontalStationDefinition::PT, pt, outgoing);
+        assert!((derived_pc.0 - pc.0).abs() < 1e-6 && (derived_pc.1 - pc.1).abs() < 1e-6);
+        let pi = from_pc.pi_coordinate(pc, incoming);
+        let derived_pi = from_pt.pi_coordinate(derived_pc, from_pt.incoming_bearing(outgoing));
+        assert!((derived_pi.0 - pi.0).abs() < 1e-6 && (derived_pi.1 - pi.1).abs() < 1e-6);
+        let via_pi = from_pt.pc_coordinate(HorizontalStationDefinition::PI, pi, incoming);
+        assert!((via_pi.0 - pc.0).abs() < 1e-6 && (via_pi.1 - pc.1).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn deflection_station_round_trip() {
+        let curve = h1_curve();
+        for row in curve.stakeout_table(50.0).rows {
+            let station = curve.station_from_deflection(row.deflection).unwrap();
+            assert!((station.value - row.station.value).abs() < 1e-6);
+        }
+
+        let past_pt = super::Angle {
+            radians: curve.dimensions.curve_angle.radians,
+        };
+        assert!(curve.station_from_deflection(past_pt).is_none());
+    }
+
+    #[test]
+    fn superelevation_transition() {
+        use super::superelevation::SuperelevationProfile;
+
+        let curve = h1_curve();
+        let profile = SuperelevationProfile::from_curve(&curve, 0.02, 0.08, 150.).unwrap();
+        let mid = (curve.stations.pc.value + curve.stations.pt.value) / 2.;
+
+        assert_eq!(profile.cross_slope_at(mid), 0.08);
+        assert_eq!(profile.cross_slope_at(9800.), -0.02);
+        assert!(profile.cross_slope_at(9900.).abs() < 1e-12);
+        assert!((profile.cross_slope_at(10000.) - 0.08 * 2. / 3.).abs() < 1e-12);
+        assert_eq!(
+            profile.cross_slope_at(curve.stations.pt.value + 200.),
+            -0.02
+        );
+        assert!((profile.runout() - 37.5).abs() < 1e-12);
+
+        assert!(SuperelevationProfile::from_curve(&curve, 0.02, 0.0, 150.).is_err());
+        assert!(SuperelevationProfile::from_curve(&curve, 0.02, 0.08, 0.0).is_err());
+    }
+
+    #[test]
+    fn pavement_area_is_centerline_length() {
+        let curve = h1_curve();
+
+        assert_eq!(
+            curve.pavement_area(24.),
+            curve.dimensions.curve_length * 24.
+        );
+    }
+
+    #[test]
+    fn short_small_angle_curve_looks_kinked() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_build_method: super::HorizontalBuildDefinition::RadiusCurveAngle,
+            input_station: "100+00".to_string(),
+            input_radius: "500".to_string(),
+            input_curve_angle: "10".to_string(),
+            ..Default::default()
+        };
+        let check = horizontal_data
+            .to_horizontal_curve()
+            .unwrap()
+            .check_minimum_length_for_appearance();
+
+        assert!(!check.passed);
+        assert!((check.required - 2000.).abs() < 1e-9);
+        assert!(check.message.contains("2000.00 ft"));
+    }
+
+    #[test]
+    fn copied_curve_compares_equal() {
+        let curve = h1_curve();
+        let mut shifted = curve;
+
+        assert_eq!(shifted, curve);
+        shifted.stations.pc.value += 1.0;
+        assert_ne!(shifted, curve);
+    }
+
+    #[test]
+    fn tangent_offsets_grow_from_pc() {
+        let curve = h1_curve();
+        let offsets = curve.tangent_offsets(50.);
+
+        assert_eq!(offsets[0], (0.0, 0.0));
+        assert!(offsets
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+        let (x_pt, y_pt) = offsets[offsets.len() - 1];
+        let half_angle = curve.dimensions.curve_angle.radians / 2.;
+        assert!((x_pt - curve.dimensions.long_chord * half_angle.cos()).abs() < 1e-6);
+        assert!((y_pt - curve.dimensions.long_chord * half_angle.sin()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn degree_of_curve_basis_follows_units() {
+        let mut horizontal_data = h1_data();
+        let us = horizontal_data.to_horizontal_curve().unwrap().dimensions;
+        horizontal_data.input_unit_system = super::UnitSystem::Metric;
+        let metric = horizontal_data.to_horizontal_curve().unwrap().dimensions;
+
+        assert!((us.degree_of_curve().radians - 100. / 818.5).abs() < 1e-12);
+        assert!(
+            (us.degree_of_curve().radians / metric.degree_of_curve().radians - 5.).abs() < 1e-3
+        );
+        assert!(metric.to_string().contains("Angle/20m: "));
+    }
+
+    #[test]
+    fn setback_for_stopping_sight() {
+        use crate::tables::get_min_sight;
+
+        let mut horizontal_data = HorizontalData {
+            input_design_speed: "65".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        let ssd = get_min_sight(
+            super::DesignSpeed::mph(65.),
+            super::DesignStandard::AASHTO,
+            super::SightType::Stopping,
+        )
+        .unwrap();
+        let setback = curve.required_setback(ssd);
+        assert!((setback - 62.73).abs() < 0.01);
+
+        horizontal_data.input_m = setback.to_string();
+        let cleared = horizontal_data.to_horizontal_curve().unwrap();
+        assert!((cleared.dimensions.sight_distance - ssd).abs() < 1e-6);
+    }
+
+    #[test]
+    fn interval_snapping_modes() {
+        use super::interval::StationSnapping;
+
+        let horizontal_data = HorizontalData {
+            input_station: "102+37.43".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+
+        let even = curve.stakeout_stations_with(25.0, StationSnapping::EvenStations);
+        assert_eq!(even, curve.stakeout_stations(25.0));
+        assert_eq!(even[1], 10250.0);
+        assert!(even[1..even.len() - 1].iter().all(|v| v % 25.0 == 0.0));
+
+        let from_pc = curve.stakeout_stations_with(25.0, StationSnapping::FromPc);
+        assert_eq!(from_pc[0], 10237.43);
+        assert!(from_pc[..from_pc.len() - 1]
+            .windows(2)
+            .all(|w| (w[1] - w[0] - 25.0).abs() < 1e-9));
+        assert_eq!(from_pc.last(), Some(&curve.stations.pt.value));
+    }
+
+    #[test]
+    fn report_sections() {
+        use super::report::DesignCriteria;
+
+        let horizontal_data = HorizontalData {
+            input_design_speed: "65".to_string(),
+            input_m: "100".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        let report = curve.report(&DesignCriteria {
+            design_standard: super::DesignStandard::AASHTO,
+            design_speed: super::DesignSpeed::mph(65.),
+            superelevation: 0.08,
+            station_interval: 50.,
+            ..Default::default()
+        });
+
+        assert!(report.contains("Radius: 818.50 ft"));
+        assert!(report.contains(&format!(
+            "Curve Length: {:.2}",
+            curve.dimensions.curve_length
+        )));
+        assert!(report.contains("Min Radius: FAIL"));
+        assert!(report.contains("Sight Distance: PASS"));
+        assert!(report.contains("Superelevation: PASS"));
+
+        let mut metric = curve;
+        metric.dimensions.unit_system = super::UnitSystem::Metric;
+        metric.dimensions.sight_distance = 170.0;
+        let report = metric.report(&DesignCriteria {
+            design_standard: super::DesignStandard::CALTRANS,
+            design_speed: super::DesignSpeed::mph(60.),
+            station_interval: 20.,
+            ..Default::default()
+        });
+        // 60 mph stops in 580 ft, 176.78 m.
+        assert!(report.contains("Sight Distance: FAIL (170.00 m against 176.78 m)"));
+    }
+
+    #[test]
+    fn constant_grade_elevation_at_pt() {
+        let curve = h1_curve();
+        let length = curve.dimensions.curve_length;
+        let pt = curve.point_with_grade(length, -0.02, 1000.);
+
+        assert!((pt.value - curve.stations.pt.value).abs() < 1e-9);
+        assert_eq!(pt.elevation, Some(1000. - 0.02 * length));
+    }
+
+    #[test]
+    fn radius_from_degree_of_curve() {
+        let mut horizontal_data = HorizontalData {
+            input_radius: "D=2d30\'".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        assert_close(curve.dimensions.radius, 2291.8312);
+        assert!((curve.dimensions.curve_length_100.to_decimal_degrees() - 2.5).abs() < 1e-9);
+
+        horizontal_data.input_radius = "D=0".to_string();
+        assert!(horizontal_data.to_horizontal_curve().is_err());
+    }
+
+    #[test]
+    fn definitions_as_map_keys() {
+        use super::{HorizontalBuildDefinition, HorizontalStationDefinition};
+        use std::collections::HashMap;
+
+        let stations = HashMap::from([
+            (HorizontalStationDefinition::PC, "PC"),
+            (HorizontalStationDefinition::PI, "PI"),
+            (HorizontalStationDefinition::PT, "PT"),
+        ]);
+        let builds = HashMap::from([
+            (HorizontalBuildDefinition::RadiusCurveAngle, 1),
+            (HorizontalBuildDefinition::RadiusTangent, 2),
+        ]);
+
+        assert_eq!(stations[&HorizontalStationDefinition::PC], "PC");
+        assert_eq!(stations[&HorizontalStationDefinition::PI], "PI");
+        assert_eq!(stations[&HorizontalStationDefinition::PT], "PT");
+        assert_eq!(builds[&HorizontalBuildDefinition::RadiusCurveAngle], 1);
+        assert_eq!(builds[&HorizontalBuildDefinition::RadiusTangent], 2);
+    }
+
+    #[test]
+    fn landxml_curve_element() -> anyhow::Result<()> {
+        let horizontal_data = HorizontalData {
+            turn: super::TurnDirection::Left,
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve()?;
+        let xml = curve.to_landxml((5000., 10000.), super::Angle::from("0d")?);
+
+        assert!(xml.starts_with("<Curve rot=\"ccw\""));
+        assert!(xml.contains("radius=\"818.5000\""));
+        assert!(xml.contains(&format!("length=\"{:.4}\"", curve.dimensions.curve_length)));
+        assert!(xml.contains("<Start>10000.0000 5000.0000</Start>"));
+        assert!(xml.contains("<Center>10000.0000 4181.5000</Center>"));
+        Ok(())
+    }
+
+    #[test]
+    fn superelevation_demand_matches_across_units() {
+        use super::superelevation::{max_speed, required_superelevation, superelevation_demand};
+
+        let us_speed = super::DesignSpeed::mph(60.);
+        let metric_speed = us_speed.to_unit(super::UnitSystem::Metric);
+        let us = superelevation_demand(us_speed, 1000.);
+        let metric = superelevation_demand(metric_speed, 304.8);
+        assert!((us - metric).abs() / us < 0.01);
+
+        let e = required_superelevation(us_speed, 1000.).unwrap();
+        assert!((e - (0.24 - 0.12)).abs() < 1e-9);
+        let v = max_speed(1000., e, 0.12, super::UnitSystem::US);
+        assert!((v.value - 60.).abs() < 1e-9);
+        let v_metric = max_speed(304.8, e, 0.12, super::UnitSystem::Metric);
+        assert!((v_metric.to_mph() - 60.).abs() < 0.3);
+    }
+
+    #[test]
+    fn chord_definition_radius_difference_grows() -> anyhow::Result<()> {
+        use super::calculate::{chord_arc_radius_difference, chord_vs_arc_radius};
+
+        let (chord, arc) = chord_vs_arc_radius(super::Angle::from("1d")?);
+        assert!((arc - 5729.58).abs() < 0.01);
+        assert!((chord - 5729.65).abs() < 0.01);
+
+        let differences = ["1d", "5d", "10d", "20d"]
+            .into_iter()
+            .map(|degree| Ok(chord_arc_radius_difference(super::Angle::from(degree)?)))
+            .collect::<anyhow::Result<Vec<f64>>>()?;
+        assert!(differences[0] > 0.0);
+        assert!(differences.windows(2).all(|w| w[0] < w[1]));
+        Ok(())
+    }
+
+    #[test]
+    fn widening_on_inside_edge() {
+        let mut horizontal_data = h1_data();
+        let right = horizontal_data.to_horizontal_curve().unwrap();
+        assert_eq!(right.widened_offsets(24., 2.), (14., -12.));
+
+        horizontal_data.turn = super::TurnDirection::Left;
+        let left = horizontal_data.to_horizontal_curve().unwrap();
+        assert_eq!(left.widened_offsets(24., 2.), (-14., 12.));
+    }
+
+    #[test]
+    fn pi_obstruction_clearance() {
+        let mut horizontal_data = h1_data();
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        assert!(curve.clears_pi_obstruction(200.));
+        assert!(!curve.clears_pi_obstruction(100.));
+
+        let radius = curve.radius_to_clear(100.);
+        assert!(radius < 818.5);
+        horizontal_data.input_radius = radius.to_string();
+        let reduced = horizontal_data.to_horizontal_curve().unwrap();
+        assert!((reduced.dimensions.external - 100.).abs() < 1e-9);
+        assert!(reduced.clears_pi_obstruction(100. + 1e-6));
+    }
+
+    #[test]
+    fn length_radius_anchored_on_pt() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PT,
+            input_build_method: super::HorizontalBuildDefinition::RadiusLength,
+            input_station: format!("109+{}", H1_CURVE_LENGTH - 900.),
+            input_radius: "818.5".to_string(),
+            input_length: H1_CURVE_LENGTH.to_string(),
+            ..Default::default()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+
+        assert_close(curve.dimensions.tangent, H1_TANGENT);
+        assert_close(curve.stations.pc.value, 10000.);
+        assert_close(curve.stations.pi.value, 10000. + H1_TANGENT);
+        assert_close(curve.stations.pt.value, 10000. + H1_CURVE_LENGTH);
+    }
+
+    #[test]
+    fn every_build_with_every_anchor() {
+        use super::{HorizontalBuildDefinition, HorizontalStationDefinition};
+
+        for build in [
+            HorizontalBuildDefinition::RadiusCurveAngle,
+            HorizontalBuildDefinition::RadiusTangent,
+            HorizontalBuildDefinition::RadiusLength,
+            HorizontalBuildDefinition::TangentCurveAngle,
+        ] {
+            for anchor in [
+                HorizontalStationDefinition::PC,
+                HorizontalStationDefinition::PI,
+                HorizontalStationDefinition::PT,
+            ] {
+                let horizontal_data = HorizontalData {
+                    input_station_method: anchor,
+                    input_build_method: build,
+                    input_station: "200+00".to_string(),
+                    input_tangent: H1_TANGENT.to_string(),
+                    input_length: H1_CURVE_LENGTH.to_string(),
+                    ..h1_data()
+                };
+                let curve = horizontal_data.to_horizontal_curve().unwrap();
+                let anchored = match anchor {
+                    HorizontalStationDefinition::PC => curve.stations.pc,
+                    HorizontalStationDefinition::PI => curve.stations.pi,
+                    HorizontalStationDefinition::PT => curve.stations.pt,
+                };
+
+                assert_close(anchored.value, 20000.);
+                assert_close(
+                    curve.stations.pi.value - curve.stations.pc.value,
+                    H1_TANGENT,
+                );
+                assert_close(
+                    curve.stations.pt.value - curve.stations.pc.value,
+                    H1_CURVE_LENGTH,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn layout_from_pi() -> anyhow::Result<()> {
+        use super::coordinates::offset;
+
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PI,
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve()?;
+        assert_eq!(curve.pc_from_pi_distance(), curve.dimensions.tangent);
+        assert_eq!(curve.pt_from_pi_distance(), curve.dimensions.tangent);
+
+        let incoming = super::Angle::from("20d")?;
+        let pi = curve.pi_coordinate((0., 0.), incoming);
+        let pc = offset(
+            pi,
+            curve.pi_to_pc_bearing(incoming).radians,
+            curve.pc_from_pi_distance(),
+        );
+        let pt = offset(
+            pi,
+            curve.pi_to_pt_bearing(incoming).radians,
+            curve.pt_from_pi_distance(),
+        );
+        let expected_pt = curve
+            .coordinate_at(curve.stations.pt.value, (0., 0.), incoming)
+            .unwrap();
+        assert!(pc.0.abs() < 1e-6 && pc.1.abs() < 1e-6);
+        assert!((pt.0 - expected_pt.0).abs() < 1e-6 && (pt.1 - expected_pt.1).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn compound_deflections_sum_to_total() -> anyhow::Result<()> {
+        use super::compound::CompoundCurve;
+        use super::coordinates::offset;
+
+        let total_delta = super::Angle::from("60d")?;
+        let compound = CompoundCurve::solve(1000., 600., total_delta, 0.4)?;
+        assert_close(
+            compound.first.dimensions.curve_angle.radians
+                + compound.second.dimensions.curve_angle.radians,
+            total_delta.radians,
+        );
+        assert_close(
+            compound.second.stations.pc.value,
+            compound.first.stations.pt.value,
+        );
+
+        // Walking PC -> V1 -> V2 -> PT must land on the tangent out of the overall PI.
+        let v1 = offset((0., 0.), 0., compound.first.dimensions.tangent);
+        let v2 = offset(
+            v1,
+            compound.first.dimensions.curve_angle.radians,
+            compound.common_tangent,
+        );
+        let pt = offset(v2, total_delta.radians, compound.second.dimensions.tangent);
+        let pi = offset((0., 0.), 0., compound.tangent_in);
+        let pt_from_pi = offset(pi, total_delta.radians, compound.tangent_out);
+        assert_close(pt.0, pt_from_pi.0);
+        assert_close(pt.1, pt_from_pi.1);
+
+        assert!(CompoundCurve::solve(1000., 600., total_delta, 1.0).is_err());
+        assert!(CompoundCurve::solve(1000., 600., total_delta, 0.0).is_err());
+        assert!(CompoundCurve::solve(
+            1000.,
+            600.,
+            super::Angle {
+                radians: -total_delta.radians
+            },
+            0.4
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn short_chord_versine() {
+        let curve = super::HorizontalCurve::from_dimensions(
+            super::HorizontalDimensions::from_radius_curve_angle(
+                1000.,
+                super::Angle::from("30d").unwrap(),
+            ),
+            0.0,
+        );
+        let exact = curve.versine(62.);
+        let approx = curve.versine_approx(62.);
+
+        assert_close(approx, 0.4805);
+        assert!(exact > approx && exact - approx < 1e-3 * exact);
+        assert_close(curve.versine(2000.), 1000.);
+    }
+
+    #[test]
+    fn implied_design_speed() {
+        use super::superelevation::design_speed_from_radius;
+
+        // Green Book minimum radii at e = 8%.
+        assert_close(
+            design_speed_from_radius(1200., 0.08, super::UnitSystem::US),
+            60.,
+        );
+        assert_close(
+            design_speed_from_radius(1e4 / (127. * 0.2), 0.08, super::UnitSystem::Metric),
+            100.,
+        );
+        let between = design_speed_from_radius(1000., 0.08, super::UnitSystem::US);
+        assert!(between > 55. && between < 60.);
+    }
+
+    #[test]
+    fn bounding_box_holds_pc_and_pt() -> anyhow::Result<()> {
+        let mut horizontal_data = HorizontalData {
+            input_curve_angle: "150d".to_string(),
+            ..h1_data()
+        };
+        let pc = (5000., 2000.);
+        let incoming = super::Angle::from("20d")?;
+        for turn in [super::TurnDirection::Right, super::TurnDirection::Left] {
+            horizontal_data.turn = turn;
+            let curve = horizontal_data.to_horizontal_curve()?;
+            let (min_x, min_y, max_x, max_y) = curve.bounding_box(pc, incoming);
+            let pt = curve
+                .coordinate_at(curve.stations.pt.value, pc, incoming)
+                .unwrap();
+            for (x, y) in [pc, pt] {
+                assert!(min_x <= x && x <= max_x && min_y <= y && y <= max_y);
+            }
+            // Turning through due east or west, the arc climbs north of both ends before coming back.
+            assert!(max_y > pc.1.max(pt.1) + 1.);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn negative_curve_length_rejected() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_station: "100+00".to_string(),
+            ..Default::default()
+        };
+        let mut dimensions = super::HorizontalDimensions::from_radius_curve_angle(
+            818.5,
+            super::Angle::from("63d15\'34\"").unwrap(),
+        );
+        assert!(horizontal_data.to_stations(&dimensions).is_ok());
+
+        for curve_length in [-100.0, 0.0, f64::NAN, f64::INFINITY] {
+            dimensions.curve_length = curve_length;
+            let error = horizontal_data.to_stations(&dimensions).unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<super::calculate::Error>(),
+                Some(super::calculate::Error::InvalidCurveLength { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn stakeout_markdown() {
+        let horizontal_data = HorizontalData {
+            input_station: "10278+21.17".to_string(),
+            ..h1_data()
+        };
+        let table = horizontal_data
+            .to_horizontal_curve()
+            .unwrap()
+            .stakeout_table(100.0);
+        let markdown = table.to_markdown();
+        let lines = markdown.lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines.len(), table.rows.len() + 2);
+        assert!(lines[0].starts_with("| Station "));
+        for header in ["Arc", "Deflection", "Chord", "Incremental Chord"] {
+            assert!(lines[0].contains(&format!("| {header} ")));
+        }
+        assert!(lines[1].starts_with("|---") && lines[1].ends_with("-|"));
+        assert!(lines[1].chars().all(|c| matches!(c, '|' | '-' | ' ')));
+        assert!(lines[2].starts_with("| 10278+21.17 "));
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    }
+
+    #[test]
+    fn curb_return_vehicles() {
+        let curb_return = super::HorizontalCurve::from_dimensions(
+            super::HorizontalDimensions::from_radius_curve_angle(
+                50.,
+                super::Angle::from("90d").unwrap(),
+            ),
+            0.0,
+        );
+
+        assert!(curb_return.accommodates_vehicle(super::DesignVehicle::P));
+        assert!(curb_return.accommodates_vehicle(super::DesignVehicle::SU));
+        assert!(!curb_return.accommodates_vehicle(super::DesignVehicle::WB67));
+    }
+
+    #[test]
+    fn curve_from_pi_and_tangent_points() -> anyhow::Result<()> {
+        use super::coordinates::offset;
+
+        let mut horizontal_data = HorizontalData {
+            input_station: "0+00".to_string(),
+            ..h1_data()
+        };
+        let incoming = super::Angle::from("20d")?;
+        for turn in [super::TurnDirection::Right, super::TurnDirection::Left] {
+            horizontal_data.turn = turn;
+            let curve = horizontal_data.to_horizontal_curve()?;
+            let pi = curve.pi_coordinate((1000., 2000.), incoming);
+            let back_point = offset(pi, incoming.radians + super::PI, 300.);
+            let ahead_point = offset(pi, curve.outgoing_bearing(incoming).radians, 1200.);
+            let rebuilt =
+                super::HorizontalCurve::from_pi_tangent_points(pi, back_point, ahead_point, 818.5)?;
+
+            assert_eq!(rebuilt.turn, turn);
+            assert_close(rebuilt.dimensions.radius, curve.dimensions.radius);
+            assert_close(rebuilt.dimensions.tangent, curve.dimensions.tangent);
+            assert_close(
+                rebuilt.dimensions.curve_angle.radians,
+                curve.dimensions.curve_angle.radians,
+            );
+            assert_close(rebuilt.stations.pt.value, curve.stations.pt.value);
+        }
+
+        let straight = super::HorizontalCurve::from_pi_tangent_points(
+            (0., 0.),
+            (0., -100.),
+            (0., 100.),
+            818.5,
+        );
+        assert!(straight.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn cache_hits_on_unchanged_inputs() -> anyhow::Result<()> {
+        use super::cache::CurveCache;
+
+        let mut horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PI,
+            input_station: "10284+50".to_string(),
+            ..h1_data()
+        };
+        let mut cache = CurveCache::default();
+        let first = cache.to_horizontal_curve(&horizontal_data)?;
+        let second = cache.to_horizontal_curve(&horizontal_data)?;
+        assert_eq!(first, second);
+        assert_eq!(first, horizontal_data.to_horizontal_curve()?);
+        assert_eq!(cache.hits(), 1);
+
+        // Unrelated fields don't invalidate the cache, curve inputs do.
+        horizontal_data.input_directory = "C:/exports".to_string();
+        cache.to_horizontal_curve(&horizontal_data)?;
+        assert_eq!(cache.hits(), 2);
+        horizontal_data.input_radius = "1000".to_string();
+        let changed = cache.to_horizontal_curve(&horizontal_data)?;
+        assert_eq!(cache.hits(), 2);
+        assert_close(changed.dimensions.radius, 1000.);
+
+        for _ in 0..1000 {
+            cache.to_horizontal_curve(&horizontal_data)?;
         }
+        assert_eq!(cache.hits(), 1002);
+        Ok(())
+    }
+
+    #[test]
+    fn setback_on_downgrade() {
+        let curve = super::HorizontalCurve::from_dimensions(
+            super::HorizontalDimensions::from_radius_curve_angle(
+                1000.,
+                super::Angle::from("60d").unwrap(),
+            ),
+            0.0,
+        );
+        let speed = super::DesignSpeed::mph(55.);
+        let on_grade = curve.required_setback_on_grade(speed, -0.05);
+
+        // SSD 540.93 ft braking down 5%, against 492.16 ft on the level.
+        assert!((on_grade - 36.36).abs() < 0.01);
+        assert!(on_grade > curve.required_setback_on_grade(speed, 0.0));
+    }
+
+    #[test]
+    fn incremental_chords_fall_short_of_arc() {
+        let horizontal_data = HorizontalData {
+            input_station: "10278+21.17".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        let chords = curve.incremental_chords(50.0);
+        let total: f64 = chords.iter().sum();
+
+        assert_eq!(chords.len(), curve.stakeout_stations(50.0).len() - 1);
+        assert!(total < curve.dimensions.curve_length);
+        assert!(curve.dimensions.curve_length - total < 0.2);
+        assert!(chords.iter().all(|chord| *chord > 0.0 && *chord <= 50.0));
+    }
+
+    #[test]
+    fn grid_rotation_places_curve() -> anyhow::Result<()> {
+        use super::coordinates::GridTransform;
+
+        let curve = h1_curve();
+        let transform = GridTransform {
+            translation: (6_000_000., 2_000_000.),
+            rotation: super::Angle::from("45d")?,
+        };
+        let (pc, bearing) = transform.placement();
+        let local = curve.coordinate_points((0., 0.), super::Angle { radians: 0.0 }, 100.0);
+        let grid = curve.coordinate_points(pc, bearing, 100.0);
+
+        assert_eq!(grid.len(), local.len());
+        for ((station, point), (local_station, local_point)) in grid.iter().zip(&local) {
+            let expected = transform.apply(*local_point);
+            assert_eq!(station.value, local_station.value);
+            assert!((point.0 - expected.0).abs() < 1e-6);
+            assert!((point.1 - expected.1).abs() < 1e-6);
+        }
+        // A point due north of the PC lands northeast of it on the grid.
+        let north = transform.apply((0., 100.));
+        assert_close(north.0 - 6_000_000., 100. / 2f64.sqrt());
+        assert_close(north.1 - 2_000_000., 100. / 2f64.sqrt());
+
+        let (min_x, min_y, max_x, max_y) = curve.bounding_box(pc, bearing);
+        for (_, point) in &grid {
+            assert!(point.0 >= min_x - 1e-6 && point.0 <= max_x + 1e-6);
+            assert!(point.1 >= min_y - 1e-6 && point.1 <= max_y + 1e-6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn corrupted_curve_fails_self_check() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PI,
+            input_station: "10284+50".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        assert_eq!(curve.is_valid(), Ok(()));
+
+        let mut corrupted = curve;
+        corrupted.dimensions.long_chord += 1.0;
+        let errors = corrupted.is_valid().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Long chord is 859.4"));
+
+        let mut corrupted = curve;
+        corrupted.dimensions.external += 1.0;
+        corrupted.dimensions.middle_ordinate -= 1.0;
+        let errors = corrupted.is_valid().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("Middle ordinate"));
+        assert!(errors[1].starts_with("External"));
+
+        // Past Δ ≈ 133.6° the PI stations ahead of the PT.
+        let wide = HorizontalData {
+            input_curve_angle: "150d".to_string(),
+            ..horizontal_data.clone()
+        }
+        .to_horizontal_curve()
+        .unwrap();
+        assert!(wide.stations.pi.value > wide.stations.pt.value);
+        assert_eq!(wide.is_valid(), Ok(()));
+
+        let mut reversed = curve;
+        reversed.stations.pc.value = curve.stations.pt.value + 1.0;
+        let errors = reversed.is_valid().unwrap_err();
+        assert!(errors[0].starts_with("Stations out of order"));
+        assert!(errors.iter().any(|e| e.starts_with("PC to PI")));
+    }
+
+    #[test]
+    fn stakeout_deflections_round_to_least_count() {
+        let horizontal_data = HorizontalData {
+            input_station: "10278+21.17".to_string(),
+            ..h1_data()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        let exact = curve.stakeout_table(50.0);
+        let rounded = curve.stakeout_table_rounded(50.0, 5);
+        assert_eq!(rounded.rows.len(), exact.rows.len());
+
+        for (exact, rounded) in exact.rows.iter().zip(&rounded.rows) {
+            let seconds = rounded.deflection.to_decimal_degrees() * 3600.;
+            assert!((seconds / 5. - (seconds / 5.).round()).abs() < 1e-6);
+            assert!((seconds - exact.deflection.to_decimal_degrees() * 3600.).abs() <= 2.5 + 1e-6);
+            assert_eq!(rounded.chord, exact.chord);
+        }
+        assert_eq!(curve.stakeout_table_rounded(50.0, 0).rows, exact.rows);
+    }
+
+    #[test]
+    fn negative_angle_turns_left() {
+        use super::{Angle, HorizontalCurve, HorizontalDimensions};
+
+        let curve =
+            HorizontalCurve::from_signed_curve_angle(1000.0, Angle::from("-30d").unwrap(), 0.0);
+        let unsigned = HorizontalCurve::from_dimensions(
+            HorizontalDimensions::from_radius_curve_angle(1000.0, Angle::from("30d").unwrap()),
+            0.0,
+        );
+
+        assert_eq!(curve.turn, super::TurnDirection::Left);
+        assert_eq!(curve.dimensions, unsigned.dimensions);
+        assert!((curve.signed_curve_angle().to_decimal_degrees() + 30.0).abs() < 1e-9);
+        let right =
+            HorizontalCurve::from_signed_curve_angle(1000.0, Angle::from("30d").unwrap(), 0.0);
+        assert_eq!(right.turn, super::TurnDirection::Right);
+        let outgoing = curve.outgoing_bearing(Angle { radians: 0.0 });
+        assert!((outgoing.to_decimal_degrees() - 330.0).abs() < 1e-9);
+
+        let signed = HorizontalData {
+            input_curve_angle: "-63d15\'34\"".to_string(),
+            ..h1_data()
+        }
+        .to_horizontal_curve()
+        .unwrap();
+        assert_eq!(signed.turn, super::TurnDirection::Left);
+        assert_eq!(signed.dimensions, h1_curve().dimensions);
+        assert_eq!(signed.stations, h1_curve().stations);
+        assert!((Angle::from("-30").unwrap().to_decimal_degrees() + 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn quarter_circle_fraction() {
+        use super::{Angle, HorizontalCurve};
+
+        let curve =
+            HorizontalCurve::from_signed_curve_angle(500.0, Angle::from("90d").unwrap(), 0.0);
+
+        assert_close(curve.circle_fraction(), 0.25);
+        assert_close(curve.arc_percent(), 25.0);
+    }
+
+    #[test]
+    fn spirals_reduce_setback() {
+        use super::{Angle, HorizontalCurve};
+
+        let curve =
+            HorizontalCurve::from_signed_curve_angle(1000.0, Angle::from("30d").unwrap(), 0.0);
+        let circular = curve.required_setback_spiraled(500.0, 0.0).unwrap();
+        let spiraled = curve.required_setback_spiraled(500.0, 200.0).unwrap();
+
+        assert!((circular - curve.required_setback(500.0)).abs() < 0.05);
+        assert!(spiraled < circular && spiraled > 0.0);
+        assert!(curve.required_setback_spiraled(500.0, 600.0).is_err());
+    }
+
+    #[test]
+    fn shift_moves_every_station() {
+        use super::{Angle, HorizontalCurve};
+
+        let original =
+            HorizontalCurve::from_signed_curve_angle(1000.0, Angle::from("30d").unwrap(), 250.0);
+        let mut shifted = original;
+        shifted.shift_stations(1000.0);
+
+        assert_eq!(
+            shifted.stations.pc.value,
+            original.stations.pc.value + 1000.0
+        );
+        assert_eq!(
+            shifted.stations.pi.value,
+            original.stations.pi.value + 1000.0
+        );
+        assert_eq!(
+            shifted.stations.pt.value,
+            original.stations.pt.value + 1000.0
+        );
+        assert_eq!(shifted.dimensions, original.dimensions);
     }
 
     #[test]
@@ -0,0 +1,104 @@
+use std::fmt::Write;
+
+use crate::horizontal::*;
+use crate::tables::{get_max_superelevation, get_min_radius, sight_distance_with_notes};
+
+/// Design controls a curve is reported against.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DesignCriteria {
+    pub design_standard: DesignStandard,
+    pub design_speed: DesignSpeed,
+    pub sight_type: SightType,
+    pub sustained_downgrade: bool,
+    /// Superelevation rate used on the curve, decimal.
+    pub superelevation: f64,
+    pub station_interval: f64,
+}
+
+fn pass_fail(passed: bool) -> &'static str {
+    match passed {
+        true => "PASS",
+        false => "FAIL",
+    }
+}
+
+impl HorizontalCurve {
+    /// Geometry, stakeout and validation sections for a design memo.
+    pub fn report(&self, criteria: &DesignCriteria) -> String {
+        let unit = self.dimensions.unit_system.length_label();
+        let mut report = String::new();
+
+        writeln!(report, "GEOMETRY").unwrap();
+        writeln!(report, "{}", self).unwrap();
+
+        writeln!(report, "STAKEOUT").unwrap();
+        let stakeout_table = self.stakeout_table(criteria.station_interval);
+        writeln!(
+            report,
+            "{} points every {:.2} {} from PC to PT",
+            stakeout_table.rows.len(),
+            criteria.station_interval,
+            unit
+        )
+        .unwrap();
+        if let Some(last) = stakeout_table.rows.last() {
+            writeln!(
+                report,
+                "Total deflection at PT: {}",
+                last.deflection.to_dms()
+            )
+            .unwrap();
+        }
+
+        writeln!(report, "\nVALIDATION").unwrap();
+        let design_speed = criteria.design_speed.to_unit(self.dimensions.unit_system);
+        match get_min_radius(design_speed, criteria.design_standard) {
+            Some(min_radius) => writeln!(
+                report,
+                "Min Radius: {} ({:.2} {unit} against {:.2} {unit})",
+                pass_fail(self.dimensions.radius >= min_radius),
+                self.dimensions.radius,
+                min_radius
+            ),
+            None => writeln!(report, "Min Radius: N/A (design speed isn't tabulated)"),
+        }
+        .unwrap();
+        // The sight distance tables are in mph and ft.
+        match sight_distance_with_notes(
+            criteria.design_speed.to_unit(UnitSystem::US),
+            criteria.design_standard,
+            criteria.sight_type,
+            criteria.sustained_downgrade,
+        ) {
+            Ok(sight_distance) => {
+                let min_sight = match self.dimensions.unit_system {
+                    UnitSystem::US => sight_distance.distance,
+                    UnitSystem::Metric => sight_distance.distance * calculate::M_PER_FT,
+                };
+                writeln!(
+                    report,
+                    "Sight Distance: {} ({:.2} {unit} against {:.2} {unit})",
+                    pass_fail(self.dimensions.sight_distance >= min_sight),
+                    self.dimensions.sight_distance,
+                    min_sight
+                )
+                .unwrap();
+                if let Some(note) = sight_distance.note {
+                    writeln!(report, "  {}", note.message).unwrap();
+                }
+            }
+            Err(e) => writeln!(report, "Sight Distance: N/A ({e})").unwrap(),
+        }
+        let e_max = get_max_superelevation(criteria.design_standard);
+        writeln!(
+            report,
+            "Superelevation: {} ({:.2}% against {:.2}% max)",
+            pass_fail(criteria.superelevation <= e_max),
+            criteria.superelevation * 100.,
+            e_max * 100.
+        )
+        .unwrap();
+
+        report
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::Result;
+
+use crate::horizontal::*;
+use crate::tables::{get_side_friction, get_side_friction_interpolated};
+
+/// Outside lane cross slope through the superelevation transitions at each end of a curve.
+/// Slopes are decimal, positive when banked toward the inside of the curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuperelevationProfile {
+    pub pc: f64,
+    pub pt: f64,
+    /// Normal crown slope, e.g. 0.02 for a 2% crown draining away from the centerline.
+    pub normal_crown: f64,
+    pub superelevation: f64,
+    /// Length over which the lane rotates from level to full superelevation.
+    pub runoff: f64,
+    /// Portion of the runoff placed on the tangent ahead of the PC and beyond the PT.
+    pub tangent_fraction: f64,
+}
+
+impl SuperelevationProfile {
+    /// Profile with the customary two thirds of the runoff on the tangent. The superelevation and
+    /// runoff must be positive.
+    pub fn from_curve(
+        curve: &HorizontalCurve,
+        normal_crown: f64,
+        superelevation: f64,
+        runoff: f64,
+    ) -> Result<Self> {
+        if superelevation <= 0.0 {
+            return Err(calculate::Error::NonPositiveDimension {
+                name: "Superelevation",
+            }
+            .into());
+        }
+        if runoff <= 0.0 {
+            return Err(calculate::Error::NonPositiveDimension { name: "Runoff" }.into());
+        }
+
+        Ok(SuperelevationProfile {
+            pc: curve.stations.pc.value,
+            pt: curve.stations.pt.value,
+            normal_crown,
+            superelevation,
+            runoff,
+            tangent_fraction: 2. / 3.,
+        })
+    }
+
+    /// Tangent runout, where the lane rotates from normal crown to level at the runoff rate.
+    pub fn runout(&self) -> f64 {
+        self.normal_crown / self.superelevation * self.runoff
+    }
+
+    pub fn cross_slope_at(&self, station: f64) -> f64 {
+        let rate = self.superelevation / self.runoff;
+        let full_start = self.pc + (1. - self.tangent_fraction) * self.runoff;
+        let full_end = self.pt - (1. - self.tangent_fraction) * self.runoff;
+        let ramp = (station - full_start).min(full_end - station);
+
+        (self.superelevation + rate * ramp.min(0.0)).max(-self.normal_crown)
+    }
+}
+
+/// Combined superelevation and side friction `e + f = V^2 / (C * R)` the design speed demands on
+/// `radius`, in ft (US) or m (Metric) to match the speed.
+pub fn superelevation_demand(design_speed: DesignSpeed, radius: f64) -> f64 {
+    design_speed.value.powi(2) / (design_speed.unit.curvature_constant() * radius)
+}
+
+/// Superelevation left after the tabulated side friction at the design speed. `None` when the
+/// speed isn't tabulated.
+pub fn required_superelevation(design_speed: DesignSpeed, radius: f64) -> Option<f64> {
+    Some(superelevation_demand(design_speed, radius) - get_side_friction(design_speed)?)
+}
+
+/// Design speed implied by an existing `radius` and `superelevation`, solving `V = sqrt(C * R * (e + f(V)))`
+/// with the side friction interpolated at the speed itself, in mph (US) or km/h (Metric).
+pub fn design_speed_from_radius(radius: f64, superelevation: f64, unit: UnitSystem) -> f64 {
+    let speed = |value: f64| DesignSpeed { value, unit };
+    let supported = |value: f64| {
+        max_speed(
+            radius,
+            superelevation,
+            get_side_friction_interpolated(speed(value)),
+            unit,
+        )
+        .value
+    };
+
+    // The supported speed falls as the trial speed rises, so bisect on where they cross.
+    let (mut low, mut high) = (0.0, supported(0.0));
+    for _ in 0..100 {
+        let mid = (low + high) / 2.;
+        match supported(mid) > mid {
+            true => low = mid,
+            false => high = mid,
+        }
+    }
+
+    (low + high) / 2.
+}
+
+/// Highest speed `V = sqrt(C * R * (e + f))` the curve supports, in mph (US) or km/h (Metric).
+pub fn max_speed(
+    radius: f64,
+    superelevation: f64,
+    side_friction: f64,
+    unit: UnitSystem,
+) -> DesignSpeed {
+    DesignSpeed {
+        value: (unit.curvature_constant() * radius * (superelevation + side_friction)).sqrt(),
+        unit,
+    }
+}
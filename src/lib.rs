@@ -1,8 +1,14 @@
 #![allow(dead_code, unused_variables)]
 
+pub mod alignment;
 pub mod datatypes;
 pub mod export;
 pub mod frontend;
 pub mod horizontal;
+pub mod prelude;
+pub mod profile;
+pub mod sight;
 pub mod tables;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod vertical;
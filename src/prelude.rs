@@ -0,0 +1,24 @@
+pub use crate::alignment::{Alignment, Segment};
+pub use crate::datatypes::{
+    Angle, AngleFormat, CurveDefinition, DesignSpeed, DesignStandard, DesignVehicle,
+    MetricStationFmt, SightType, Station, StationEquation, StationFmt, UnitSystem,
+};
+pub use crate::horizontal::calculate::{
+    CurveCheck, HorizontalCurve, HorizontalDimensions, HorizontalStations,
+};
+pub use crate::horizontal::compound::CompoundCurve;
+pub use crate::horizontal::coordinates::{Coordinate, GridTransform};
+pub use crate::horizontal::display::CurveSummaryRow;
+pub use crate::horizontal::interval::{StakeoutRow, StakeoutTable, StationSnapping};
+pub use crate::horizontal::report::DesignCriteria;
+pub use crate::horizontal::superelevation::SuperelevationProfile;
+pub use crate::horizontal::{
+    HorizontalBuildDefinition, HorizontalData, HorizontalStationDefinition, TurnDirection,
+};
+pub use crate::profile::{Profile, ProfileBuilder};
+pub use crate::sight::{DowngradeMethod, SightDistanceQuery, SsdParameters};
+pub use crate::tables::SightError;
+pub use crate::vertical::calculate::{
+    SightHeights, VerticalCurve, VerticalDimensions, VerticalStations,
+};
+pub use crate::vertical::{ObstacleType, VerticalData, VerticalDefinition};
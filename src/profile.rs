@@ -0,0 +1,365 @@
+use anyhow::Result;
+
+use crate::datatypes::{DesignSpeed, DesignStandard, SightType, Station};
+use crate::tables::get_min_sight;
+use crate::vertical::calculate::VerticalCurve;
+
+/// AASHTO passing sight distance eye and object heights, in ft.
+const PASSING_EYE_HEIGHT: f64 = 3.5;
+const PASSING_OBJECT_HEIGHT: f64 = 3.5;
+/// Spacing of the stations sight lines are checked between, in ft.
+const SIGHT_STEP: f64 = 5.0;
+
+/// Vertical curves ordered by station, joined by the tangent grades between them.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    pub curves: Vec<VerticalCurve>,
+}
+
+/// Two consecutive vertical curves where the first PVT lies past the next PVC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveOverlap {
+    pub first: usize,
+    pub second: usize,
+    pub length: f64,
+}
+
+impl Profile {
+    /// Tangent grade between curves, or the curve grade within one. `None` for an empty profile.
+    pub fn grade_at(&self, station: f64) -> Option<f64> {
+        let first = self.curves.first()?;
+        if station < first.stations.pvc.value {
+            return Some(first.dimensions.incoming_grade);
+        }
+        let mut grade = first.dimensions.incoming_grade;
+        for curve in &self.curves {
+            if station < curve.stations.pvc.value {
+                break;
+            }
+            grade = curve
+                .grade_at(station)
+                .unwrap_or(curve.dimensions.outgoing_grade);
+        }
+
+        Some(grade)
+    }
+
+    /// Curve elevation within a curve, or the tangent projected from the nearest curve before it.
+    /// `None` for an empty profile or a curve without a PVC elevation.
+    pub fn elevation_at(&self, station: f64) -> Option<f64> {
+        let first = self.curves.first()?;
+        if station < first.stations.pvc.value {
+            return Some(
+                first.stations.pvc.elevation?
+                    - first.dimensions.incoming_grade * (first.stations.pvc.value - station),
+            );
+        }
+        let mut elevation = None;
+        for curve in &self.curves {
+            if station < curve.stations.pvc.value {
+                break;
+            }
+            elevation = curve.elevation_at(station).or_else(|| {
+                Some(
+                    curve.stations.pvt.elevation?
+                        + curve.dimensions.outgoing_grade * (station - curve.stations.pvt.value),
+                )
+            });
+        }
+
+        elevation
+    }
+
+    /// Distance ahead of `station`, up to `max_distance`, over which an object `object_height` tall
+    /// stays visible from an eye `eye_height` above the profile.
+    pub fn available_sight_distance(
+        &self,
+        station: f64,
+        eye_height: f64,
+        object_height: f64,
+        max_distance: f64,
+    ) -> Option<f64> {
+        let eye = self.elevation_at(station)? + eye_height;
+        let mut steepest_obstruction = f64::NEG_INFINITY;
+        let mut distance = SIGHT_STEP;
+        while distance <= max_distance {
+            let ground = self.elevation_at(station + distance)?;
+            if (ground + object_height - eye) / distance < steepest_obstruction {
+                return Some(distance - SIGHT_STEP);
+            }
+            steepest_obstruction = steepest_obstruction.max((ground - eye) / distance);
+            distance += SIGHT_STEP;
+        }
+
+        Some(max_distance)
+    }
+
+    pub fn overlapping_curves(&self) -> Vec<CurveOverlap> {
+        self.curves
+            .windows(2)
+            .enumerate()
+            .filter_map(|(i, pair)| {
+                let length = pair[0].stations.pvt.value - pair[1].stations.pvc.value;
+                match length > 0.0 {
+                    true => Some(CurveOverlap {
+                        first: i,
+                        second: i + 1,
+                        length,
+                    }),
+                    false => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Collects grade points, the PVIs of a profile, and fits a vertical curve at each interior one.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileBuilder {
+    grade_points: Vec<(Station, f64)>,
+}
+
+impl ProfileBuilder {
+    pub fn grade_point(mut self, station: f64, elevation: f64) -> Self {
+        self.grade_points.push((
+            Station {
+                value: station,
+                elevation: Some(elevation),
+                ..Default::default()
+            },
+            0.0,
+        ));
+        self
+    }
+
+    /// Length of the vertical curve at the last grade point added.
+    pub fn curve_length(mut self, curve_length: f64) -> Self {
+        if let Some(grade_point) = self.grade_points.last_mut() {
+            grade_point.1 = curve_length;
+        }
+        self
+    }
+
+    pub fn build(self) -> Result<Profile> {
+        let points = &self.grade_points;
+        if points.len() < 3 {
+            return Err(Error::TooFewGradePoints(points.len()).into());
+        }
+        if let Some(i) = points.windows(2).position(|w| w[1].0.value <= w[0].0.value) {
+            return Err(Error::StationsOutOfOrder(i + 1).into());
+        }
+        let last = points.len() - 1;
+        if let Some(i) = [0, last].into_iter().find(|i| points[*i].1 != 0.0) {
+            return Err(Error::CurveAtEndPoint(i).into());
+        }
+        let grade = |from: &(Station, f64), to: &(Station, f64)| {
+            (to.0.elevation.unwrap_or_default() - from.0.elevation.unwrap_or_default())
+                / (to.0.value - from.0.value)
+        };
+
+        let mut curves = vec![];
+        for i in 1..last {
+            let (pvi, curve_length) = points[i];
+            if curve_length <= 0.0 {
+                return Err(Error::MissingCurveLength(i).into());
+            }
+            let curve = VerticalCurve::from_pvi(
+                pvi,
+                grade(&points[i - 1], &points[i]),
+                grade(&points[i], &points[i + 1]),
+                curve_length,
+            );
+            let previous_end = curves
+                .last()
+                .map_or(points[0].0.value, |c: &VerticalCurve| c.stations.pvt.value);
+            if curve.stations.pvc.value < previous_end
+                || curve.stations.pvt.value > points[i + 1].0.value
+            {
+                return Err(Error::CurveTooLong(i).into());
+            }
+            curves.push(curve);
+        }
+
+        Ok(Profile { curves })
+    }
+}
+
+/// Station ranges, looking ahead up station, where the available passing sight distance falls short of
+/// the AASHTO passing sight distance for `design_speed`. `None` when the speed isn't tabulated.
+pub fn no_passing_zones(
+    profile: &Profile,
+    design_speed: DesignSpeed,
+) -> Option<Vec<(Station, Station)>> {
+    let required = get_min_sight(design_speed, DesignStandard::AASHTO, SightType::Passing).ok()?;
+    let (Some(first), Some(last)) = (profile.curves.first(), profile.curves.last()) else {
+        return Some(vec![]);
+    };
+    let station = |value: f64| Station {
+        value,
+        ..Default::default()
+    };
+
+    let mut zones = vec![];
+    let mut zone_start = None;
+    let mut running = first.stations.pvc.value - required;
+    while running <= last.stations.pvt.value {
+        let available = profile.available_sight_distance(
+            running,
+            PASSING_EYE_HEIGHT,
+            PASSING_OBJECT_HEIGHT,
+            required,
+        )?;
+        match (available < required, zone_start) {
+            (true, None) => zone_start = Some(running),
+            (false, Some(start)) => {
+                zones.push((station(start), station(running - SIGHT_STEP)));
+                zone_start = None;
+            }
+            _ => {}
+        }
+        running += SIGHT_STEP;
+    }
+    if let Some(start) = zone_start {
+        zones.push((station(start), station(running - SIGHT_STEP)));
+    }
+
+    Some(zones)
+}
+
+/// Profile Errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A profile needs grade points at both ends and at least one PVI between them.
+    #[error("Profile needs at least 3 grade points, found {0}.")]
+    TooFewGradePoints(usize),
+    /// Grade point stations must increase.
+    #[error("Grade point {0} isn't up station of the one before it.")]
+    StationsOutOfOrder(usize),
+    /// The first and last grade points are the ends of the profile.
+    #[error("Grade point {0} is an end of the profile and can't have a curve.")]
+    CurveAtEndPoint(usize),
+    /// Interior grade points need a curve length.
+    #[error("Grade point {0} needs a curve length greater than zero.")]
+    MissingCurveLength(usize),
+    /// Curve runs past a neighbouring grade point or into the curve before it.
+    #[error("Curve at grade point {0} overlaps its neighbours.")]
+    CurveTooLong(usize),
+}
+
+#[cfg(test)]
+mod profile_tests {
+    use super::{no_passing_zones, CurveOverlap, Error, Profile, ProfileBuilder};
+    use crate::datatypes::DesignSpeed;
+    use crate::vertical::{VerticalData, VerticalDefinition};
+
+    fn curve(station: &str, incoming: &str, outgoing: &str, length: &str) -> VerticalData {
+        VerticalData {
+            input_method: VerticalDefinition::PVI,
+            input_station: station.to_string(),
+            input_elevation: "1000".to_string(),
+            input_incoming_grade: incoming.to_string(),
+            input_outgoing_grade: outgoing.to_string(),
+            input_length: length.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn close_pvis_overlap() {
+        let profile = Profile {
+            curves: vec![
+                curve("10+00", "2%", "-1%", "400")
+                    .to_vertical_curve()
+                    .unwrap(),
+                curve("13+00", "-1%", "3%", "300")
+                    .to_vertical_curve()
+                    .unwrap(),
+            ],
+        };
+
+        assert_eq!(
+            profile.overlapping_curves(),
+            vec![CurveOverlap {
+                first: 0,
+                second: 1,
+                length: 50.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn builder_fits_curves_at_interior_pvis() {
+        let profile = ProfileBuilder::default()
+            .grade_point(1000., 100.)
+            .grade_point(2000., 120.)
+            .curve_length(400.)
+            .grade_point(3000., 100.)
+            .curve_length(300.)
+            .grade_point(4000., 130.)
+            .build()
+            .unwrap();
+
+        assert_eq!(profile.curves.len(), 2);
+        let (crest, sag) = (&profile.curves[0], &profile.curves[1]);
+        assert!((crest.dimensions.incoming_grade - 0.02).abs() < 1e-12);
+        assert!((crest.dimensions.outgoing_grade + 0.02).abs() < 1e-12);
+        assert!((sag.dimensions.outgoing_grade - 0.03).abs() < 1e-12);
+        assert_eq!(crest.stations.pvc.value, 1800.);
+        assert_eq!(sag.stations.pvt.value, 3150.);
+        assert!((crest.stations.pvc.elevation.unwrap() - 116.).abs() < 1e-9);
+        assert!((profile.elevation_at(2000.).unwrap() - 118.).abs() < 1e-9);
+        assert!((profile.grade_at(2500.).unwrap() + 0.02).abs() < 1e-12);
+
+        let missing = ProfileBuilder::default()
+            .grade_point(1000., 100.)
+            .grade_point(2000., 120.)
+            .grade_point(3000., 100.)
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            missing.downcast_ref::<Error>(),
+            Some(Error::MissingCurveLength(1))
+        ));
+    }
+
+    #[test]
+    fn grade_across_tangent_curve_tangent() {
+        let profile = Profile {
+            curves: vec![curve("10+00", "2%", "-2%", "400")
+                .to_vertical_curve()
+                .unwrap()],
+        };
+
+        assert_eq!(profile.grade_at(500.0), Some(0.02));
+        assert_eq!(profile.grade_at(800.0), Some(0.02));
+        assert!((profile.grade_at(1000.0).unwrap()).abs() < 1e-12);
+        assert!((profile.grade_at(1100.0).unwrap() + 0.01).abs() < 1e-12);
+        assert_eq!(profile.grade_at(1500.0), Some(-0.02));
+        assert_eq!(Profile::default().grade_at(1000.0), None);
+    }
+
+    #[test]
+    fn crest_creates_no_passing_zone() {
+        let crest = Profile {
+            curves: vec![curve("20+00", "2%", "-2%", "400")
+                .to_vertical_curve()
+                .unwrap()],
+        };
+        let zones = no_passing_zones(&crest, DesignSpeed::mph(65.)).unwrap();
+
+        assert_eq!(zones.len(), 1);
+        let (start, end) = zones[0];
+        // Looking up station, the crest hides the road beyond it from the approach until the eye nears the PVI.
+        assert!(start.value < 1800.0 && end.value > 1800.0 && end.value < 2000.0);
+        assert!(
+            crest.available_sight_distance(start.value - 5.0, 3.5, 3.5, 1100.0) >= Some(1100.0)
+        );
+
+        let sag = Profile {
+            curves: vec![curve("20+00", "-2%", "2%", "400")
+                .to_vertical_curve()
+                .unwrap()],
+        };
+        assert_eq!(no_passing_zones(&sag, DesignSpeed::mph(65.)), Some(vec![]));
+    }
+}
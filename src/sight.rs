@@ -0,0 +1,366 @@
+use crate::datatypes::{calc_adjustment, DesignSpeed, DesignStandard, SightType, UnitSystem};
+use crate::tables::{
+    get_min_sight, get_min_sight_interpolated, SightError, SightNote, SUSTAINED_DOWNGRADE_NOTE,
+};
+
+const FPS_PER_MPH: f64 = 5280. / 3600.;
+const MPS_PER_KPH: f64 = 1000. / 3600.;
+
+/// Driver and braking assumptions for stopping sight distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SsdParameters {
+    /// Perception-reaction time in seconds.
+    pub reaction_time: f64,
+    /// Deceleration rate in ft/s² (US) or m/s² (Metric).
+    pub deceleration: f64,
+}
+
+impl Default for SsdParameters {
+    fn default() -> Self {
+        SsdParameters::aashto(UnitSystem::US)
+    }
+}
+
+impl SsdParameters {
+    /// AASHTO Green Book values, 2.5 s and 11.2 ft/s² (3.4 m/s²).
+    pub fn aashto(unit: UnitSystem) -> Self {
+        match unit {
+            UnitSystem::US => SsdParameters {
+                reaction_time: 2.5,
+                deceleration: 11.2,
+            },
+            UnitSystem::Metric => SsdParameters {
+                reaction_time: 2.5,
+                deceleration: 3.4,
+            },
+        }
+    }
+}
+
+/// Speed in ft/s or m/s and gravitational acceleration for the speed's unit system.
+fn velocity_and_gravity(design_speed: DesignSpeed) -> (f64, f64) {
+    match design_speed.unit {
+        UnitSystem::US => (design_speed.value * FPS_PER_MPH, 32.2),
+        UnitSystem::Metric => (design_speed.value * MPS_PER_KPH, 9.81),
+    }
+}
+
+/// Brake reaction plus braking distance on `grade` (decimal, negative for downgrades),
+/// in ft or m to match the design speed.
+pub fn stopping_sight_distance(
+    design_speed: DesignSpeed,
+    grade: f64,
+    parameters: SsdParameters,
+) -> f64 {
+    let (velocity, gravity) = velocity_and_gravity(design_speed);
+
+    velocity * parameters.reaction_time
+        + velocity.powi(2) / (2. * gravity * (parameters.deceleration / gravity + grade))
+}
+
+/// `stopping_sight_distance` while braking through a curve of `radius` (ft or m) with decimal
+/// `superelevation`. Superelevation carries `e` of the side friction demand `V^2 / (gR)` (Green Book
+/// point-mass relation `e + f = V^2 / (15R)`) and the rest is taken out of the deceleration, which
+/// shares the tire friction circle with it (NCHRP Report 774). `None` when the side friction demand
+/// leaves no deceleration.
+pub fn stopping_sight_distance_on_curve(
+    design_speed: DesignSpeed,
+    grade: f64,
+    radius: f64,
+    superelevation: f64,
+    parameters: SsdParameters,
+) -> Option<f64> {
+    let (velocity, gravity) = velocity_and_gravity(design_speed);
+    let lateral = gravity * (velocity.powi(2) / (gravity * radius) - superelevation).max(0.0);
+    if lateral >= parameters.deceleration {
+        return None;
+    }
+
+    let deceleration = (parameters.deceleration.powi(2) - lateral.powi(2)).sqrt();
+    Some(stopping_sight_distance(
+        design_speed,
+        grade,
+        SsdParameters {
+            deceleration,
+            ..parameters
+        },
+    ))
+}
+
+/// Leg of the departure sight triangle along the major road, `ISD = 1.47 * V * t` (`0.278 * V * t` metric)
+/// for a gap acceptance time `gap_time` in seconds, in ft or m to match the design speed.
+pub fn intersection_sight_distance(major_speed: DesignSpeed, gap_time: f64) -> f64 {
+    match major_speed.unit {
+        UnitSystem::US => 1.47 * major_speed.value * gap_time,
+        UnitSystem::Metric => 0.278 * major_speed.value * gap_time,
+    }
+}
+
+/// How a downgrade lengthens the tabulated stopping sight distance.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DowngradeMethod {
+    /// Only the flat 20% sustained downgrade increase.
+    TableFactor,
+    /// Only the analytic stopping distance on the approach grade, even when it falls under the table
+    /// value. The table value still applies without a downgrade.
+    Analytic,
+    /// The longer of the two, so the result is never under-conservative.
+    #[default]
+    Conservative,
+}
+
+/// Bundles the inputs of a minimum sight distance lookup.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SightDistanceQuery {
+    design_speed: DesignSpeed,
+    sight_type: SightType,
+    sustained_downgrade: bool,
+    grade: Option<f64>,
+    design_standard: DesignStandard,
+    downgrade_method: DowngradeMethod,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SightQueryResult {
+    pub distance: f64,
+    pub notes: Vec<SightNote>,
+}
+
+impl SightDistanceQuery {
+    pub fn design_speed(mut self, design_speed: DesignSpeed) -> Self {
+        self.design_speed = design_speed;
+        self
+    }
+
+    pub fn sight_type(mut self, sight_type: SightType) -> Self {
+        self.sight_type = sight_type;
+        self
+    }
+
+    pub fn sustained_downgrade(mut self, sustained_downgrade: bool) -> Self {
+        self.sustained_downgrade = sustained_downgrade;
+        self
+    }
+
+    /// Approach grade in percent, e.g. `-4.0` for a 4% downgrade. Stored as a decimal.
+    pub fn grade(mut self, grade: f64) -> Self {
+        self.grade = Some(grade / 100.);
+        self
+    }
+
+    pub fn standard(mut self, design_standard: DesignStandard) -> Self {
+        self.design_standard = design_standard;
+        self
+    }
+
+    pub fn downgrade_method(mut self, downgrade_method: DowngradeMethod) -> Self {
+        self.downgrade_method = downgrade_method;
+        self
+    }
+
+    /// Table distance, interpolated between speeds, with the sustained downgrade increase and
+    /// the analytic stopping distance on a downgrade applied as the downgrade method allows.
+    /// Errors when the speed or sight type isn't tabulated for the standard.
+    pub fn solve(&self) -> Result<SightQueryResult, SightError> {
+        let mut notes = vec![];
+        let mut distance =
+            get_min_sight_interpolated(self.design_speed, self.design_standard, self.sight_type)?;
+        if get_min_sight(self.design_speed, self.design_standard, self.sight_type).is_err() {
+            notes.push(SightNote {
+                message: format!(
+                    "{} isn't tabulated, sight distance interpolated.",
+                    self.design_speed
+                ),
+            });
+        }
+        if !matches!(self.sight_type, SightType::Stopping) {
+            return Ok(SightQueryResult { distance, notes });
+        }
+
+        let use_factor = self.downgrade_method != DowngradeMethod::Analytic;
+        let use_analytic = self.downgrade_method != DowngradeMethod::TableFactor;
+        if self.sustained_downgrade && use_factor {
+            distance *= calc_adjustment(self.sustained_downgrade);
+            notes.push(SightNote {
+                message: SUSTAINED_DOWNGRADE_NOTE.to_string(),
+            });
+        }
+        if let Some(grade) = self.grade.filter(|grade| *grade < 0.0 && use_analytic) {
+            let on_grade = stopping_sight_distance(
+                DesignSpeed::mph(self.design_speed.to_mph()),
+                grade,
+                SsdParameters::default(),
+            );
+            let replaces = match self.downgrade_method {
+                DowngradeMethod::Analytic => true,
+                _ => on_grade > distance,
+            };
+            if replaces {
+                distance = on_grade;
+                notes.push(SightNote {
+                    message: format!(
+                        "{}% downgrade sets stopping sight distance to {:.2} ft.",
+                        grade * 100.,
+                        on_grade
+                    ),
+                });
+            }
+        }
+
+        Ok(SightQueryResult { distance, notes })
+    }
+}
+
+#[cfg(test)]
+mod sight_tests {
+    use super::{
+        intersection_sight_distance, stopping_sight_distance, stopping_sight_distance_on_curve,
+        DowngradeMethod, SightDistanceQuery, SightError, SsdParameters,
+    };
+    use crate::datatypes::{DesignSpeed, DesignStandard, SightType};
+
+    #[test]
+    fn aashto_defaults() {
+        let ssd = stopping_sight_distance(DesignSpeed::mph(60.), 0.0, SsdParameters::default());
+        assert!((ssd - 565.71).abs() < 0.01);
+    }
+
+    #[test]
+    fn shorter_reaction_time() {
+        let speed = DesignSpeed::mph(60.);
+        let aashto = stopping_sight_distance(speed, 0.0, SsdParameters::default());
+        let quick = stopping_sight_distance(
+            speed,
+            0.0,
+            SsdParameters {
+                reaction_time: 2.0,
+                ..Default::default()
+            },
+        );
+
+        assert!(quick < aashto);
+        assert!((aashto - quick - 0.5 * 88.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn superelevation_offsets_curve_braking() {
+        let speed = DesignSpeed::mph(60.);
+        let parameters = SsdParameters::default();
+        let tangent = stopping_sight_distance(speed, -0.04, parameters);
+        let banked =
+            stopping_sight_distance_on_curve(speed, -0.04, 1000., 0.08, parameters).unwrap();
+        let flat = stopping_sight_distance_on_curve(speed, -0.04, 1000., 0.0, parameters).unwrap();
+
+        // 88 ft/s on R = 1000 ft pulls 7.74 ft/s² sideways, 2.58 ft/s² of it from the 8% bank.
+        let deceleration = (11.2_f64.powi(2) - (7744_f64 / 1000. - 0.08 * 32.2).powi(2)).sqrt();
+        let expected = stopping_sight_distance(
+            speed,
+            -0.04,
+            SsdParameters {
+                deceleration,
+                ..parameters
+            },
+        );
+        assert!((banked - expected).abs() < 1e-9);
+        assert!(tangent < banked && banked < flat);
+
+        // Superelevation covering the whole demand leaves the full deceleration.
+        let gentle = stopping_sight_distance_on_curve(speed, -0.04, 5000., 0.08, parameters);
+        assert!((gentle.unwrap() - tangent).abs() < 1e-9);
+        assert!(stopping_sight_distance_on_curve(speed, -0.04, 500., 0.0, parameters).is_none());
+    }
+
+    #[test]
+    fn query_builder_solves() {
+        let query = SightDistanceQuery::default()
+            .design_speed(DesignSpeed::mph(65.))
+            .sight_type(SightType::Stopping)
+            .standard(DesignStandard::CALTRANS);
+        let level = query.solve().unwrap();
+        assert_eq!(level.distance, 660.0);
+        assert!(level.notes.is_empty());
+
+        let downgrade = query.sustained_downgrade(true).grade(-4.0).solve().unwrap();
+        assert!((downgrade.distance - 792.0).abs() < 1e-9);
+        assert_eq!(downgrade.notes.len(), 1);
+
+        let steep = query.grade(-9.0).solve().unwrap();
+        assert!(steep.distance > 660.0);
+        assert_eq!(steep.notes.len(), 1);
+
+        let between = query.design_speed(DesignSpeed::mph(62.)).solve().unwrap();
+        assert!((between.distance - (580.0 + 80.0 * 2. / 5.)).abs() < 1e-9);
+        assert_eq!(between.notes.len(), 1);
+
+        assert!(query.sight_type(SightType::Decision).solve().is_ok());
+        assert!(matches!(
+            query
+                .standard(DesignStandard::AASHTO)
+                .sight_type(SightType::Decision)
+                .solve(),
+            Err(SightError::TableNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn conservative_takes_longer_method() {
+        let query = SightDistanceQuery::default()
+            .design_speed(DesignSpeed::mph(65.))
+            .sight_type(SightType::Stopping)
+            .standard(DesignStandard::CALTRANS)
+            .sustained_downgrade(true);
+        let solve = |grade: f64, downgrade_method| {
+            query
+                .grade(grade)
+                .downgrade_method(downgrade_method)
+                .solve()
+                .unwrap()
+                .distance
+        };
+
+        // Steep grade, the analytic distance controls.
+        let factor = solve(-12.0, DowngradeMethod::TableFactor);
+        let analytic = solve(-12.0, DowngradeMethod::Analytic);
+        assert!((factor - 792.0).abs() < 1e-9);
+        assert!(analytic > factor);
+        assert_eq!(solve(-12.0, DowngradeMethod::Conservative), analytic);
+
+        // Mild grade, the 20% increase controls.
+        let factor = solve(-4.0, DowngradeMethod::TableFactor);
+        let analytic = solve(-4.0, DowngradeMethod::Analytic);
+        assert!(analytic < factor && analytic > 660.0);
+        assert_eq!(solve(-4.0, DowngradeMethod::Conservative), factor);
+
+        // Slight grade, the analytic distance falls under the table value.
+        let level = query.sustained_downgrade(false);
+        let analytic = level
+            .grade(-0.5)
+            .downgrade_method(DowngradeMethod::Analytic)
+            .solve()
+            .unwrap();
+        assert!(analytic.distance < 660.0);
+        assert_eq!(analytic.notes.len(), 1);
+        let conservative = level.grade(-0.5).solve().unwrap();
+        assert_eq!(conservative.distance, 660.0);
+        assert!(conservative.notes.is_empty());
+    }
+
+    #[test]
+    fn speed_past_table() {
+        let query = SightDistanceQuery::default()
+            .design_speed(DesignSpeed::mph(95.))
+            .standard(DesignStandard::CALTRANS);
+        assert!(matches!(
+            query.solve(),
+            Err(SightError::SpeedOutOfRange { design_speed }) if design_speed == DesignSpeed::mph(95.)
+        ));
+    }
+
+    #[test]
+    fn left_turn_from_stop() {
+        // Green Book Table 9-7, passenger car turning left with a 7.5 s gap.
+        let isd = intersection_sight_distance(DesignSpeed::mph(50.), 7.5);
+        assert!((isd - 551.25).abs() < 1e-9);
+        assert!((intersection_sight_distance(DesignSpeed::kph(80.), 7.5) - 166.8).abs() < 1e-9);
+    }
+}
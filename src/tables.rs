@@ -1,8 +1,42 @@
 // use anyhow::{Result, anyhow};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
+use std::io::BufRead;
 
-use crate::datatypes::{DesignStandard, SightType};
+use crate::datatypes::{
+    calc_adjustment, DesignSpeed, DesignStandard, DesignVehicle, SightType, UnitSystem,
+};
+
+/// Sight Distance Errors
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SightError {
+    /// The standard doesn't tabulate the sight type.
+    #[error("{design_standard:?} doesn't tabulate {sight_type:?} sight distance.")]
+    TableNotFound {
+        design_standard: DesignStandard,
+        sight_type: SightType,
+    },
+    /// Design speed isn't in the table.
+    #[error("{design_speed} is outside the sight distance table.")]
+    SpeedOutOfRange { design_speed: DesignSpeed },
+    /// Table row couldn't be read.
+    #[error("Line {line}: invalid row \"{content}\".")]
+    ParseError { line: usize, content: String },
+    /// Table row has no distance columns.
+    #[error("Line {line}: row has no distances.")]
+    ColumnMissing { line: usize },
+    /// The sight distance tables are in mph and ft only.
+    #[error("{design_standard:?} sight distances are tabulated in mph, convert {design_speed} with `to_unit(UnitSystem::US)` first.")]
+    UnitMismatch {
+        design_speed: DesignSpeed,
+        design_standard: DesignStandard,
+    },
+    /// Custom table has no column for the sight type.
+    #[error("{sight_type:?} sight distance data not available in this table.")]
+    SightTypeNotInTable { sight_type: SightType },
+}
+
+const M_PER_FT: f64 = 0.3048;
 
 //AASHTO GREEN BOOK TABLE 3-35
 lazy_static! {
@@ -51,27 +85,661 @@ lazy_static! {
     };
 }
 
+//AASHTO GREEN BOOK TABLE 3-34 & TABLE 3-36
+lazy_static! {
+    static ref AASHTO_K_TABLE: HashMap<i32, (f64, f64)> = { //crest K, sag K
+        let mut m = HashMap::new();
+        m.insert(15, (3.0, 10.0));
+        m.insert(20, (7.0, 17.0));
+        m.insert(25, (12.0, 26.0));
+        m.insert(30, (19.0, 37.0));
+        m.insert(35, (29.0, 49.0));
+        m.insert(40, (44.0, 64.0));
+        m.insert(45, (61.0, 79.0));
+        m.insert(50, (84.0, 96.0));
+        m.insert(55, (114.0, 115.0));
+        m.insert(60, (151.0, 136.0));
+        m.insert(65, (193.0, 157.0));
+        m.insert(70, (247.0, 181.0));
+        m.insert(75, (312.0, 206.0));
+        m.insert(80, (384.0, 231.0));
+
+        m
+    };
+}
+
+//AASHTO GREEN BOOK TABLE 3-7
+lazy_static! {
+    static ref AASHTO_SIDE_FRICTION_TABLE: HashMap<i32, f64> = { //mph, f
+        let mut m = HashMap::new();
+        m.insert(15, 0.32);
+        m.insert(20, 0.27);
+        m.insert(25, 0.23);
+        m.insert(30, 0.20);
+        m.insert(35, 0.18);
+        m.insert(40, 0.16);
+        m.insert(45, 0.15);
+        m.insert(50, 0.14);
+        m.insert(55, 0.13);
+        m.insert(60, 0.12);
+        m.insert(65, 0.11);
+        m.insert(70, 0.10);
+        m.insert(75, 0.09);
+        m.insert(80, 0.08);
+
+        m
+    };
+}
+
+//AASHTO GREEN BOOK TABLE 3-7 (METRIC)
+lazy_static! {
+    static ref AASHTO_SIDE_FRICTION_TABLE_METRIC: HashMap<i32, f64> = { //km/h, f
+        let mut m = HashMap::new();
+        m.insert(20, 0.35);
+        m.insert(30, 0.28);
+        m.insert(40, 0.23);
+        m.insert(50, 0.19);
+        m.insert(60, 0.17);
+        m.insert(70, 0.15);
+        m.insert(80, 0.14);
+        m.insert(90, 0.13);
+        m.insert(100, 0.12);
+        m.insert(110, 0.11);
+        m.insert(120, 0.09);
+        m.insert(130, 0.08);
+
+        m
+    };
+}
+
+fn side_friction_table(unit: UnitSystem) -> &'static HashMap<i32, f64> {
+    match unit {
+        UnitSystem::US => &AASHTO_SIDE_FRICTION_TABLE,
+        UnitSystem::Metric => &AASHTO_SIDE_FRICTION_TABLE_METRIC,
+    }
+}
+
+pub fn get_side_friction(design_speed: DesignSpeed) -> Option<f64> {
+    side_friction_table(design_speed.unit)
+        .get(&design_speed.table_key(design_speed.unit)?)
+        .copied()
+}
+
+/// Side friction interpolated linearly between table speeds, held at the end rows outside the table.
+pub fn get_side_friction_interpolated(design_speed: DesignSpeed) -> f64 {
+    let mut rows = side_friction_table(design_speed.unit)
+        .iter()
+        .map(|(speed, f)| (*speed as f64, *f))
+        .collect::<Vec<(f64, f64)>>();
+    rows.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (first, last) = (rows[0], rows[rows.len() - 1]);
+    if design_speed.value <= first.0 {
+        return first.1;
+    }
+
+    rows.windows(2)
+        .find(|w| design_speed.value <= w[1].0)
+        .map(|w| w[0].1 + (w[1].1 - w[0].1) * (design_speed.value - w[0].0) / (w[1].0 - w[0].0))
+        .unwrap_or(last.1)
+}
+
+/// Approximate smallest simple curve radius, in ft, that keeps the design vehicle on the traveled way
+/// through a 90° turn. Larger vehicles are usually given three-centered curves instead.
+pub fn get_min_turning_radius(design_vehicle: DesignVehicle) -> f64 {
+    match design_vehicle {
+        DesignVehicle::P => 40.0,
+        DesignVehicle::SU => 50.0,
+        DesignVehicle::WB40 => 75.0,
+        DesignVehicle::WB67 => 120.0,
+    }
+}
+
+/// Maximum superelevation rate, 8% for AASHTO and 12% for the Caltrans HDM.
+pub fn get_max_superelevation(design_standard: DesignStandard) -> f64 {
+    match design_standard {
+        DesignStandard::AASHTO => 0.08,
+        DesignStandard::CALTRANS => 0.12,
+    }
+}
+
+/// Minimum radius `R = V^2 / (C * (e_max + f))` at the design speed, in ft (US) or m (Metric).
+pub fn get_min_radius(design_speed: DesignSpeed, design_standard: DesignStandard) -> Option<f64> {
+    let constant = design_speed.unit.curvature_constant();
+    let f = get_side_friction(design_speed)?;
+
+    Some(design_speed.value.powi(2) / (constant * (get_max_superelevation(design_standard) + f)))
+}
+
+/// Minimum radius `R = V^2 / (C * (e_max + f))` for every tabulated design speed, sorted by speed.
+pub fn radius_table(design_standard: DesignStandard, unit: UnitSystem) -> Vec<(DesignSpeed, f64)> {
+    let constant = unit.curvature_constant();
+    let e_max = get_max_superelevation(design_standard);
+    let mut rows = side_friction_table(unit)
+        .iter()
+        .map(|(speed, f)| {
+            (
+                DesignSpeed {
+                    value: *speed as f64,
+                    unit,
+                },
+                (*speed as f64).powi(2) / (constant * (e_max + f)),
+            )
+        })
+        .collect::<Vec<(DesignSpeed, f64)>>();
+    rows.sort_by(|a, b| a.0.value.total_cmp(&b.0.value));
+
+    rows
+}
+
+pub fn get_k_value(design_speed: DesignSpeed, crest: bool) -> Option<f64> {
+    let row = AASHTO_K_TABLE.get(&design_speed.table_key(UnitSystem::US)?)?;
+    match crest {
+        true => Some(row.0),
+        false => Some(row.1),
+    }
+}
+
 pub fn get_min_sight(
-    design_speed: i32,
+    design_speed: DesignSpeed,
     design_standard: DesignStandard,
     sight_type: SightType,
-) -> Option<f64> {
+) -> Result<f64, SightError> {
+    if design_speed.unit != UnitSystem::US {
+        return Err(SightError::UnitMismatch {
+            design_speed,
+            design_standard,
+        });
+    }
+    let out_of_range = SightError::SpeedOutOfRange { design_speed };
+    let key = design_speed
+        .table_key(UnitSystem::US)
+        .ok_or(out_of_range.clone())?;
     match design_standard {
         DesignStandard::AASHTO => {
-            let row = AASHTO_SIGHT_TABLE.get(&design_speed)?;
+            let row = AASHTO_SIGHT_TABLE.get(&key);
             match sight_type {
-                SightType::Stopping => Some(row.0),
-                SightType::Passing => Some(row.1),
-                SightType::Decision => None,
+                SightType::Stopping => row.map(|row| row.0).ok_or(out_of_range),
+                SightType::Passing => row.map(|row| row.1).ok_or(out_of_range),
+                SightType::Decision => Err(SightError::TableNotFound {
+                    design_standard,
+                    sight_type,
+                }),
             }
         }
         DesignStandard::CALTRANS => {
-            let row = HDM_SIGHT_TABLE.get(&design_speed)?;
+            let row = HDM_SIGHT_TABLE.get(&key).ok_or(out_of_range)?;
             match sight_type {
-                SightType::Stopping => Some(row.0),
-                SightType::Passing => Some(row.1),
-                SightType::Decision => Some(row.2),
+                SightType::Stopping => Ok(row.0),
+                SightType::Passing => Ok(row.1),
+                SightType::Decision => Ok(row.2),
+            }
+        }
+    }
+}
+
+/// Minimum sight distance, interpolated linearly between the 5 mph table rows when the design speed falls between them.
+pub fn get_min_sight_interpolated(
+    design_speed: DesignSpeed,
+    design_standard: DesignStandard,
+    sight_type: SightType,
+) -> Result<f64, SightError> {
+    match get_min_sight(design_speed, design_standard, sight_type) {
+        Err(SightError::SpeedOutOfRange { .. }) => {}
+        min_sight => return min_sight,
+    }
+    let out_of_range = |_| SightError::SpeedOutOfRange { design_speed };
+    let mph = design_speed.to_mph();
+    let lower = (mph / 5.0).floor() * 5.0;
+    let lower_sight = get_min_sight(DesignSpeed::mph(lower), design_standard, sight_type)
+        .map_err(out_of_range)?;
+    let upper_sight = get_min_sight(DesignSpeed::mph(lower + 5.0), design_standard, sight_type)
+        .map_err(out_of_range)?;
+
+    Ok(lower_sight + (upper_sight - lower_sight) * (mph - lower) / 5.0)
+}
+
+pub(crate) const SUSTAINED_DOWNGRADE_NOTE: &str =
+    ">3% downgrade longer than 1 mile detected, stopping sight distance increased by 20%.";
+
+/// Advisory attached to a sight distance lookup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SightNote {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SightDistance {
+    pub distance: f64,
+    pub note: Option<SightNote>,
+}
+
+/// Looks up the minimum sight distance, applying the HDM 20% sustained downgrade increase to stopping sight distance.
+pub fn sight_distance_with_notes(
+    design_speed: DesignSpeed,
+    design_standard: DesignStandard,
+    sight_type: SightType,
+    sustained_downgrade: bool,
+) -> Result<SightDistance, SightError> {
+    let min_sight = get_min_sight(design_speed, design_standard, sight_type)?;
+    match (sight_type, sustained_downgrade) {
+        (SightType::Stopping, true) => Ok(SightDistance {
+            distance: min_sight * calc_adjustment(sustained_downgrade),
+            note: Some(SightNote {
+                message: SUSTAINED_DOWNGRADE_NOTE.to_string(),
+            }),
+        }),
+        _ => Ok(SightDistance {
+            distance: min_sight,
+            note: None,
+        }),
+    }
+}
+
+/// Stopping, passing and decision sight distances for one design speed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SightDistances {
+    pub stopping: f64,
+    pub passing: f64,
+    /// `None` when the standard doesn't tabulate decision sight distance.
+    pub decision: Option<f64>,
+}
+
+/// All three minimum sight distances at `design_speed`, each interpolated between table rows.
+pub fn all_sight_distances(
+    design_speed: DesignSpeed,
+    design_standard: DesignStandard,
+) -> Result<SightDistances, SightError> {
+    let lookup = |sight_type| get_min_sight_interpolated(design_speed, design_standard, sight_type);
+    let decision = match lookup(SightType::Decision) {
+        Ok(decision) => Some(decision),
+        Err(SightError::TableNotFound { .. }) => None,
+        Err(e) => return Err(e),
+    };
+
+    Ok(SightDistances {
+        stopping: lookup(SightType::Stopping)?,
+        passing: lookup(SightType::Passing)?,
+        decision,
+    })
+}
+
+/// Minimum sight distance every `step` from `start` to `end` inclusive, speeds in the units of `unit`,
+/// interpolated between table rows. Metric speeds are converted to look up the mph table and their
+/// distances returned in m. Speeds outside the table are left out.
+pub fn sight_distance_range(
+    design_standard: DesignStandard,
+    sight_type: SightType,
+    start: i32,
+    end: i32,
+    step: i32,
+    unit: UnitSystem,
+) -> Vec<(i32, f64)> {
+    if step <= 0 {
+        return vec![];
+    }
+
+    (start..=end)
+        .step_by(step as usize)
+        .filter_map(|speed| {
+            let design_speed = DesignSpeed {
+                value: speed as f64,
+                unit,
+            }
+            .to_unit(UnitSystem::US);
+            let min_sight =
+                get_min_sight_interpolated(design_speed, design_standard, sight_type).ok()?;
+            match unit {
+                UnitSystem::US => Some((speed, min_sight)),
+                UnitSystem::Metric => Some((speed, min_sight * M_PER_FT)),
+            }
+        })
+        .collect()
+}
+
+/// The longest, and so governing, minimum sight distance among `sight_types`. Types the standard
+/// doesn't tabulate are skipped; the first lookup error when none of them are.
+pub fn controlling_sight_distance(
+    design_speed: DesignSpeed,
+    sight_types: &[SightType],
+    design_standard: DesignStandard,
+    sustained_downgrade: bool,
+) -> Result<(SightType, f64), SightError> {
+    let mut governing: Option<(SightType, f64)> = None;
+    let mut first_error = None;
+    for sight_type in sight_types {
+        match sight_distance_with_notes(
+            design_speed,
+            design_standard,
+            *sight_type,
+            sustained_downgrade,
+        ) {
+            Ok(sight_distance) => {
+                if governing.is_none_or(|(_, distance)| sight_distance.distance > distance) {
+                    governing = Some((*sight_type, sight_distance.distance));
+                }
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
             }
         }
     }
+
+    governing.ok_or(first_error.unwrap_or(SightError::SpeedOutOfRange { design_speed }))
+}
+
+/// Reads a custom sight distance table line by line, one `speed, distance, distance, ...` row per line.
+/// Blank lines and lines starting with `#` are skipped. Errors carry the 1-based line number.
+pub fn parse_table_checked<R: BufRead>(reader: R) -> Result<HashMap<i32, Vec<f64>>, SightError> {
+    let mut table = HashMap::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.map_err(|e| SightError::ParseError {
+            line: line_number,
+            content: e.to_string(),
+        })?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parse_error = || SightError::ParseError {
+            line: line_number,
+            content: line.to_string(),
+        };
+        let mut cells = line.split(',').map(str::trim);
+        let speed = cells
+            .next()
+            .unwrap_or_default()
+            .parse::<i32>()
+            .map_err(|_| parse_error())?;
+        let distances = cells
+            .map(|cell| cell.parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|_| parse_error())?;
+        if distances.is_empty() {
+            return Err(SightError::ColumnMissing { line: line_number });
+        }
+        if table.insert(speed, distances).is_some() {
+            return Err(parse_error());
+        }
+    }
+
+    Ok(table)
+}
+
+/// Sight distance from a parsed custom table, whose columns are stopping, passing, then decision.
+/// Tables may stop after any column.
+pub fn get_custom_sight(
+    table: &HashMap<i32, Vec<f64>>,
+    design_speed: DesignSpeed,
+    sight_type: SightType,
+) -> Result<f64, SightError> {
+    let row = design_speed
+        .table_key(design_speed.unit)
+        .and_then(|key| table.get(&key))
+        .ok_or(SightError::SpeedOutOfRange { design_speed })?;
+    let column = match sight_type {
+        SightType::Stopping => 0,
+        SightType::Passing => 1,
+        SightType::Decision => 2,
+    };
+
+    row.get(column)
+        .copied()
+        .ok_or(SightError::SightTypeNotInTable { sight_type })
+}
+
+/// Design speeds a parsed custom table contains, in ascending order.
+pub fn table_speeds(table: &HashMap<i32, Vec<f64>>) -> Vec<i32> {
+    let mut speeds: Vec<i32> = table.keys().copied().collect();
+    speeds.sort_unstable();
+    speeds
+}
+
+#[cfg(test)]
+mod table_tests {
+    use super::{
+        all_sight_distances, controlling_sight_distance, get_custom_sight, get_min_sight,
+        get_min_sight_interpolated, parse_table_checked, radius_table, sight_distance_range,
+        sight_distance_with_notes, table_speeds, SightError,
+    };
+    use crate::datatypes::{DesignSpeed, DesignStandard, SightType, UnitSystem};
+
+    #[test]
+    fn all_three_distances_at_once() {
+        let hdm = all_sight_distances(DesignSpeed::mph(62.), DesignStandard::CALTRANS).unwrap();
+        assert_eq!(
+            hdm.stopping,
+            get_min_sight_interpolated(
+                DesignSpeed::mph(62.),
+                DesignStandard::CALTRANS,
+                SightType::Stopping
+            )
+            .unwrap()
+        );
+        assert!(hdm.passing > hdm.stopping);
+        assert!(hdm.decision.is_some_and(|decision| decision > hdm.stopping));
+
+        let aashto = all_sight_distances(DesignSpeed::mph(60.), DesignStandard::AASHTO).unwrap();
+        assert!(aashto.passing > aashto.stopping);
+        assert_eq!(aashto.decision, None);
+        assert!(matches!(
+            all_sight_distances(DesignSpeed::mph(95.), DesignStandard::CALTRANS),
+            Err(SightError::SpeedOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn downgrade_note_on_stopping_only() {
+        let flat = sight_distance_with_notes(
+            DesignSpeed::mph(65.),
+            DesignStandard::CALTRANS,
+            SightType::Stopping,
+            false,
+        )
+        .unwrap();
+        assert_eq!(flat.distance, 660.0);
+        assert!(flat.note.is_none());
+
+        let downgrade = sight_distance_with_notes(
+            DesignSpeed::mph(65.),
+            DesignStandard::CALTRANS,
+            SightType::Stopping,
+            true,
+        )
+        .unwrap();
+        assert!((downgrade.distance - 792.0).abs() < 1e-9);
+        assert!(downgrade.note.is_some());
+
+        let passing = sight_distance_with_notes(
+            DesignSpeed::mph(65.),
+            DesignStandard::CALTRANS,
+            SightType::Passing,
+            true,
+        )
+        .unwrap();
+        assert!(passing.note.is_none());
+    }
+
+    #[test]
+    fn min_radius_increases_with_speed() {
+        for design_standard in [DesignStandard::AASHTO, DesignStandard::CALTRANS] {
+            for unit in [UnitSystem::US, UnitSystem::Metric] {
+                let rows = radius_table(design_standard, unit);
+                assert!(!rows.is_empty());
+                assert!(rows
+                    .windows(2)
+                    .all(|w| w[0].0.value < w[1].0.value && w[0].1 < w[1].1));
+            }
+        }
+        let rows = radius_table(DesignStandard::AASHTO, UnitSystem::US);
+        let (_, r60) = rows
+            .iter()
+            .find(|row| row.0 == DesignSpeed::mph(60.))
+            .unwrap();
+        assert!((r60 - 1200.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn custom_table_reports_bad_line() {
+        let good = "# speed, decision\n50, 1030\n55, 1115\n";
+        let table = parse_table_checked(good.as_bytes()).unwrap();
+        assert_eq!(table.get(&55), Some(&vec![1115.0]));
+
+        let bad = "50, 1030\n55, 1115\n60, 12o0\n65, 1410\n";
+        assert!(matches!(
+            parse_table_checked(bad.as_bytes()),
+            Err(SightError::ParseError { line: 3, content }) if content == "60, 12o0"
+        ));
+
+        let short = "50, 1030\n\n55\n";
+        assert!(matches!(
+            parse_table_checked(short.as_bytes()),
+            Err(SightError::ColumnMissing { line: 3 })
+        ));
+    }
+
+    #[test]
+    fn stopping_only_table() {
+        let stopping_only = "# speed, stopping\n50, 425\n55, 495\n";
+        let table = parse_table_checked(stopping_only.as_bytes()).unwrap();
+
+        assert_eq!(
+            get_custom_sight(&table, DesignSpeed::mph(55.), SightType::Stopping).unwrap(),
+            495.0
+        );
+        let passing = get_custom_sight(&table, DesignSpeed::mph(55.), SightType::Passing);
+        assert!(matches!(
+            passing,
+            Err(SightError::SightTypeNotInTable {
+                sight_type: SightType::Passing
+            })
+        ));
+        assert_eq!(
+            passing.unwrap_err().to_string(),
+            "Passing sight distance data not available in this table."
+        );
+        assert!(matches!(
+            get_custom_sight(&table, DesignSpeed::mph(60.), SightType::Stopping),
+            Err(SightError::SpeedOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn table_speeds_sorted() {
+        let shuffled = "65, 1410\n# note\n50, 1030\n60, 1280\n55, 1115\n";
+        let table = parse_table_checked(shuffled.as_bytes()).unwrap();
+        assert_eq!(table_speeds(&table), vec![50, 55, 60, 65]);
+    }
+
+    #[test]
+    fn decision_governs_stopping() {
+        let types = [SightType::Stopping, SightType::Decision];
+        let governing = controlling_sight_distance(
+            DesignSpeed::mph(65.),
+            &types,
+            DesignStandard::CALTRANS,
+            false,
+        );
+        assert!(matches!(governing, Ok((SightType::Decision, d)) if d == 1050.0));
+
+        let aashto = controlling_sight_distance(
+            DesignSpeed::mph(65.),
+            &types,
+            DesignStandard::AASHTO,
+            false,
+        );
+        assert!(matches!(aashto, Ok((SightType::Stopping, d)) if d == 645.0));
+        assert!(controlling_sight_distance(
+            DesignSpeed::mph(65.),
+            &[SightType::Decision],
+            DesignStandard::AASHTO,
+            false
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn metric_speed_against_us_table() {
+        let error = get_min_sight_interpolated(
+            DesignSpeed::kph(100.),
+            DesignStandard::CALTRANS,
+            SightType::Stopping,
+        )
+        .unwrap_err();
+        assert!(matches!(error, SightError::UnitMismatch { .. }));
+        assert!(error.to_string().contains("tabulated in mph"));
+
+        let converted = get_min_sight_interpolated(
+            DesignSpeed::kph(100.).to_unit(UnitSystem::US),
+            DesignStandard::CALTRANS,
+            SightType::Stopping,
+        );
+        assert!(converted.is_ok());
+    }
+
+    #[test]
+    fn speed_out_of_range() {
+        assert!(matches!(
+            get_min_sight(
+                DesignSpeed::mph(95.),
+                DesignStandard::AASHTO,
+                SightType::Stopping
+            ),
+            Err(SightError::SpeedOutOfRange { .. })
+        ));
+        assert!(matches!(
+            get_min_sight_interpolated(
+                DesignSpeed::mph(82.),
+                DesignStandard::AASHTO,
+                SightType::Stopping
+            ),
+            Err(SightError::SpeedOutOfRange { design_speed }) if design_speed == DesignSpeed::mph(82.)
+        ));
+        assert!(matches!(
+            get_min_sight(
+                DesignSpeed::mph(95.),
+                DesignStandard::AASHTO,
+                SightType::Decision
+            ),
+            Err(SightError::TableNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn sight_distance_series() {
+        let series = sight_distance_range(
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            25,
+            80,
+            5,
+            UnitSystem::US,
+        );
+        assert_eq!(series.len(), 12);
+        assert_eq!(series.first(), Some(&(25, 155.0)));
+        assert_eq!(series.last(), Some(&(80, 910.0)));
+
+        let fine = sight_distance_range(
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            60,
+            65,
+            1,
+            UnitSystem::US,
+        );
+        assert_eq!(fine.len(), 6);
+        assert!(fine.windows(2).all(|w| w[0].1 < w[1].1));
+
+        let metric = sight_distance_range(
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            40,
+            130,
+            10,
+            UnitSystem::Metric,
+        );
+        // 130 km/h is past the 80 mph row.
+        assert_eq!(metric.len(), 9);
+        assert_eq!(metric.last().map(|row| row.0), Some(120));
+    }
 }
@@ -0,0 +1,118 @@
+use crate::horizontal::calculate::HorizontalCurve;
+
+/// Panics naming the first dimension or station of `a` and `b` that differs by more than `eps`.
+pub fn assert_curve_approx_eq(a: &HorizontalCurve, b: &HorizontalCurve, eps: f64) {
+    let fields = [
+        (
+            "dimensions.radius",
+            a.dimensions.radius,
+            b.dimensions.radius,
+        ),
+        (
+            "dimensions.curve_length",
+            a.dimensions.curve_length,
+            b.dimensions.curve_length,
+        ),
+        (
+            "dimensions.tangent",
+            a.dimensions.tangent,
+            b.dimensions.tangent,
+        ),
+        (
+            "dimensions.long_chord",
+            a.dimensions.long_chord,
+            b.dimensions.long_chord,
+        ),
+        (
+            "dimensions.middle_ordinate",
+            a.dimensions.middle_ordinate,
+            b.dimensions.middle_ordinate,
+        ),
+        (
+            "dimensions.external",
+            a.dimensions.external,
+            b.dimensions.external,
+        ),
+        (
+            "dimensions.curve_length_100",
+            a.dimensions.curve_length_100.radians,
+            b.dimensions.curve_length_100.radians,
+        ),
+        (
+            "dimensions.curve_angle",
+            a.dimensions.curve_angle.radians,
+            b.dimensions.curve_angle.radians,
+        ),
+        (
+            "dimensions.design_speed",
+            a.dimensions.design_speed.value,
+            b.dimensions.design_speed.value,
+        ),
+        (
+            "dimensions.sight_distance",
+            a.dimensions.sight_distance,
+            b.dimensions.sight_distance,
+        ),
+        ("stations.pc", a.stations.pc.value, b.stations.pc.value),
+        ("stations.pi", a.stations.pi.value, b.stations.pi.value),
+        ("stations.pt", a.stations.pt.value, b.stations.pt.value),
+    ];
+
+    for (name, left, right) in fields {
+        if (left - right).abs() > eps || left.is_nan() != right.is_nan() {
+            panic!(
+                "curves differ at {}: {} != {} (eps {})",
+                name, left, right, eps
+            );
+        }
+    }
+    if a.dimensions.design_speed.unit != b.dimensions.design_speed.unit {
+        panic!(
+            "curves differ at dimensions.design_speed.unit: {:?} != {:?}",
+            a.dimensions.design_speed.unit, b.dimensions.design_speed.unit
+        );
+    }
+    if a.dimensions.unit_system != b.dimensions.unit_system {
+        panic!(
+            "curves differ at dimensions.unit_system: {:?} != {:?}",
+            a.dimensions.unit_system, b.dimensions.unit_system
+        );
+    }
+    if a.turn != b.turn {
+        panic!("curves differ at turn: {:?} != {:?}", a.turn, b.turn);
+    }
+}
+
+#[cfg(test)]
+mod testing_tests {
+    use super::assert_curve_approx_eq;
+    use crate::datatypes::{Angle, UnitSystem};
+    use crate::horizontal::calculate::{HorizontalCurve, HorizontalDimensions};
+
+    fn curve(radius: f64) -> HorizontalCurve {
+        HorizontalCurve::from_dimensions(
+            HorizontalDimensions::from_radius_curve_angle(radius, Angle { radians: 0.5 }),
+            1000.,
+        )
+    }
+
+    #[test]
+    fn equal_curves_pass() {
+        assert_curve_approx_eq(&curve(818.5), &curve(818.5), 1e-9);
+        assert_curve_approx_eq(&curve(818.5), &curve(818.5 + 1e-12), 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "curves differ at dimensions.radius")]
+    fn differing_radius_fails() {
+        assert_curve_approx_eq(&curve(818.5), &curve(820.), 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "curves differ at dimensions.unit_system")]
+    fn differing_unit_system_fails() {
+        let mut metric = curve(818.5);
+        metric.dimensions.unit_system = UnitSystem::Metric;
+        assert_curve_approx_eq(&curve(818.5), &metric, 1e-9);
+    }
+}
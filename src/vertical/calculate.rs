@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result};
 
-use crate::tables::get_min_sight;
+use crate::tables::{get_k_value, get_min_sight};
 use crate::vertical::*;
 
 pub type ObstacleReturn = Result<(bool, ObstacleStation, Station, f64), Error>;
@@ -18,7 +18,7 @@ pub struct VerticalDimensions {
     pub outgoing_grade: f64,
     pub curve_length: f64,
     pub external: f64,
-    pub design_speed: i32,
+    pub design_speed: DesignSpeed,
     pub sustained_downgrade: bool,
 }
 
@@ -28,7 +28,76 @@ pub struct VerticalCurve {
     pub stations: VerticalStations,
 }
 
+/// Driver eye and object heights in ft for crest sight distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SightHeights {
+    pub eye_height: f64,
+    pub object_height: f64,
+}
+
+impl Default for SightHeights {
+    fn default() -> Self {
+        SightHeights::passenger_car()
+    }
+}
+
+impl SightHeights {
+    /// AASHTO stopping sight distance values, 3.5 ft eye and 2.0 ft object.
+    pub fn passenger_car() -> Self {
+        SightHeights {
+            eye_height: 3.5,
+            object_height: 2.0,
+        }
+    }
+
+    /// Truck driver seated higher, 7.6 ft eye and 2.0 ft object.
+    pub fn truck() -> Self {
+        SightHeights {
+            eye_height: 7.6,
+            object_height: 2.0,
+        }
+    }
+}
+
 impl VerticalCurve {
+    /// Symmetric curve of `curve_length` centered on `pvi`, which must carry an elevation.
+    pub fn from_pvi(
+        pvi: Station,
+        incoming_grade: f64,
+        outgoing_grade: f64,
+        curve_length: f64,
+    ) -> VerticalCurve {
+        let half = curve_length / 2.0;
+        let a = (outgoing_grade - incoming_grade) / (2.0 * curve_length);
+        let station = |value: f64, elevation: Option<f64>| Station {
+            value,
+            elevation,
+            ..Default::default()
+        };
+
+        VerticalCurve {
+            dimensions: VerticalDimensions {
+                incoming_grade,
+                outgoing_grade,
+                curve_length,
+                external: a * half.powi(2),
+                design_speed: DesignSpeed::default(),
+                sustained_downgrade: false,
+            },
+            stations: VerticalStations {
+                pvc: station(
+                    pvi.value - half,
+                    pvi.elevation.map(|e| e - incoming_grade * half),
+                ),
+                pvi,
+                pvt: station(
+                    pvi.value + half,
+                    pvi.elevation.map(|e| e + outgoing_grade * half),
+                ),
+            },
+        }
+    }
+
     pub fn calc_min_curve_length(
         &self,
         min_sight: f64,
@@ -37,18 +106,18 @@ impl VerticalCurve {
     ) -> Result<f64> {
         let curve_length = self.dimensions.curve_length;
         let grade_break = self.dimensions.outgoing_grade - self.dimensions.incoming_grade;
-        let design_speed = self.dimensions.design_speed;
+        let design_speed = self.dimensions.design_speed.to_mph();
         let a = grade_break.abs() * 100.0;
         if grade_break == 0.0 {
             // --
             return Ok(0.0);
         }
-        let mut min_sight_adjusted = min_sight.clone();
-        if a >= 2. && design_speed >= 40 && min_sight_adjusted < 10. * design_speed as f64 {
-            min_sight_adjusted = 10. * design_speed as f64;
+        let mut min_sight_adjusted = min_sight;
+        if a >= 2. && design_speed >= 40. && min_sight_adjusted < 10. * design_speed {
+            min_sight_adjusted = 10. * design_speed;
             println!("large min used.");
         }
-        if a < 2. && design_speed < 40 && min_sight_adjusted < 200.0 {
+        if a < 2. && design_speed < 40. && min_sight_adjusted < 200.0 {
             min_sight_adjusted = 200.;
             println!("small min used.");
         }
@@ -220,7 +289,7 @@ impl VerticalCurve {
     ) -> Result<(bool, f64)> {
         let min_sight = get_min_sight(self.dimensions.design_speed, design_standard, sight_type);
         match min_sight {
-            Some(w) => {
+            Ok(w) => {
                 let min_curve_length =
                     self.calc_min_curve_length(w * adjustment, design_standard, sight_type)?;
 
@@ -229,7 +298,7 @@ impl VerticalCurve {
                     min_curve_length,
                 ))
             }
-            None => Err(anyhow!("Design speed isn't specified in the manual.")),
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -270,22 +339,101 @@ impl VerticalCurve {
     }
 
     fn spot_station_with_station(&self, station: Station) -> Result<Station, Error> {
-        if station.value >= self.stations.pvc.value && station.value <= self.stations.pvt.value {
-            let distance_delta = station.value - self.stations.pvc.value;
-            let a = (self.dimensions.outgoing_grade - self.dimensions.incoming_grade)
-                / (2.0 * self.dimensions.curve_length);
-            let elevation = self.stations.pvc.elevation.unwrap()
-                + self.dimensions.incoming_grade * distance_delta
-                + a * distance_delta.powi(2);
-            return Ok(Station {
+        match self.elevation_at(station.value) {
+            Some(elevation) => Ok(Station {
                 value: station.value,
                 elevation: Some(elevation),
                 ..Default::default()
-            });
+            }),
+            None => Err(Error::ParseStation { station }),
+        }
+    }
+
+    /// Headlight sight distance in ft on a sag curve, from a 2.0 ft headlight with a 1° upward beam
+    /// (`L = A * S² / (400 + 3.5 * S)` when `S < L`). `None` for crest curves.
+    pub fn sag_sight_distance_headlight(&self) -> Option<f64> {
+        let grade_break = self.dimensions.outgoing_grade - self.dimensions.incoming_grade;
+        if grade_break <= 0.0 {
+            return None;
+        }
+        let a = grade_break * 100.0;
+        let l = self.dimensions.curve_length;
+        let s = (3.5 * l + ((3.5 * l).powi(2) + 1600.0 * a * l).sqrt()) / (2.0 * a);
+        if s <= l {
+            return Some(s);
         }
 
-        Err(Error::ParseStation { station })
+        // S > L: L = 2S - (400 + 3.5S) / A, the beam clears the far tangent when A <= 1.75.
+        match a > 1.75 {
+            true => Some((a * l + 400.0) / (2.0 * a - 3.5)),
+            false => Some(f64::INFINITY),
+        }
     }
+
+    /// Sight distance in ft over a crest curve for `heights`, solving `L = A * S² / (200 * (√h1 + √h2)²)`
+    /// when `S < L` and `L = 2S - 200 * (√h1 + √h2)² / A` otherwise. `None` for sag curves.
+    pub fn available_sight_distance(&self, heights: SightHeights) -> Option<f64> {
+        let grade_break = self.dimensions.incoming_grade - self.dimensions.outgoing_grade;
+        if grade_break <= 0.0 {
+            return None;
+        }
+        let a = grade_break * 100.0;
+        let l = self.dimensions.curve_length;
+        let clearance = 200.0 * (heights.eye_height.sqrt() + heights.object_height.sqrt()).powi(2);
+        let s = (l * clearance / a).sqrt();
+
+        match s < l {
+            true => Some(s),
+            false => Some((l + clearance / a) / 2.0),
+        }
+    }
+
+    /// Sag curve length in ft for rider comfort, `L = A * V² / 46.5` with `V` in mph.
+    pub fn sag_comfort_length(&self, design_speed: DesignSpeed) -> f64 {
+        let a = (self.dimensions.outgoing_grade - self.dimensions.incoming_grade).abs() * 100.0;
+
+        a * design_speed.to_mph().powi(2) / 46.5
+    }
+
+    /// Curve grade at `station`, or `None` outside PVC to PVT.
+    pub fn grade_at(&self, station: f64) -> Option<f64> {
+        if station < self.stations.pvc.value || station > self.stations.pvt.value {
+            return None;
+        }
+        let distance_delta = station - self.stations.pvc.value;
+
+        Some(
+            self.dimensions.incoming_grade
+                + (self.dimensions.outgoing_grade - self.dimensions.incoming_grade)
+                    * distance_delta
+                    / self.dimensions.curve_length,
+        )
+    }
+
+    /// Curve elevation at `station`, or `None` outside PVC to PVT.
+    pub fn elevation_at(&self, station: f64) -> Option<f64> {
+        if station < self.stations.pvc.value || station > self.stations.pvt.value {
+            return None;
+        }
+        let distance_delta = station - self.stations.pvc.value;
+        let a = (self.dimensions.outgoing_grade - self.dimensions.incoming_grade)
+            / (2.0 * self.dimensions.curve_length);
+
+        Some(
+            self.stations.pvc.elevation?
+                + self.dimensions.incoming_grade * distance_delta
+                + a * distance_delta.powi(2),
+        )
+    }
+}
+
+/// Minimum curve length `L = K * A` for stopping sight distance, with `A` in percent.
+pub fn min_vertical_curve_length(
+    design_speed: DesignSpeed,
+    a_percent: f64,
+    crest: bool,
+) -> Option<f64> {
+    Some(get_k_value(design_speed, crest)? * a_percent.abs())
 }
 
 /// Vertical Calculate Errors.
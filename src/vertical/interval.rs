@@ -51,4 +51,62 @@ impl VerticalCurve {
 
         curve_detail
     }
+
+    /// PVC, every even multiple of `station_interval`, the turning point when it falls on the curve, and PVT.
+    pub fn profile_points(&self, station_interval: f64) -> Vec<Station> {
+        let pvc = self.stations.pvc.value;
+        let pvt = self.stations.pvt.value;
+        let mut values = vec![pvc, pvt];
+
+        if station_interval > 0.0 {
+            let mut running = ((pvc / station_interval).floor() + 1.0) * station_interval;
+            while running < pvt {
+                values.push(running);
+                running += station_interval;
+            }
+        }
+        let extreme = self.get_extreme().value;
+        if extreme.is_finite() && extreme > pvc && extreme < pvt {
+            values.push(extreme);
+        }
+        values.sort_by(f64::total_cmp);
+        values.dedup();
+
+        values
+            .into_iter()
+            .map(|value| Station {
+                value,
+                elevation: self.elevation_at(value),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// (station, elevation, grade) at the PVC, every even multiple of `station_interval`, and the PVT.
+    /// Empty when the PVC has no elevation.
+    pub fn grade_table(&self, station_interval: f64) -> Vec<(Station, f64, f64)> {
+        let pvc = self.stations.pvc.value;
+        let pvt = self.stations.pvt.value;
+        let mut values = vec![pvc];
+
+        if station_interval > 0.0 {
+            let mut running = ((pvc / station_interval).floor() + 1.0) * station_interval;
+            while running < pvt {
+                values.push(running);
+                running += station_interval;
+            }
+        }
+        values.push(pvt);
+
+        values
+            .into_iter()
+            .filter_map(|value| {
+                let station = Station {
+                    value,
+                    ..Default::default()
+                };
+                Some((station, self.elevation_at(value)?, self.grade_at(value)?))
+            })
+            .collect()
+    }
 }
@@ -70,7 +70,8 @@ impl VerticalData {
         let curve_length = coerce_length(&self.input_length)?;
         let a = (outgoing_grade - incoming_grade) / (2.0 * curve_length);
         let external = a * (curve_length / 2.0).powi(2);
-        let design_speed = coerce_speed(&self.input_design_speed).unwrap_or_default();
+        let design_speed =
+            coerce_speed(&self.input_design_speed, UnitSystem::US).unwrap_or_default();
         let sustained_downgrade = self.sustained_downgrade;
 
         Ok(VerticalDimensions {
@@ -176,12 +177,26 @@ impl VerticalData {
     }
 }
 
+/// Decimal grade `(e2 - e1) / (s2 - s1)` from `a` to `b`. `None` when either station has no
+/// elevation or both share a station.
+pub fn grade_between(a: &Station, b: &Station) -> Option<f64> {
+    let run = b.value - a.value;
+    if run == 0.0 {
+        return None;
+    }
+
+    Some((b.elevation? - a.elevation?) / run)
+}
+
 #[cfg(test)]
 mod vertical_tests {
 
-    use crate::datatypes::Station;
+    use crate::datatypes::{DesignSpeed, Station};
 
-    use super::{VerticalData, VerticalDefinition};
+    use super::{
+        calculate::{min_vertical_curve_length, SightHeights},
+        grade_between, VerticalData, VerticalDefinition,
+    };
 
     #[test]
     fn v1() {
@@ -332,4 +347,164 @@ mod vertical_tests {
             }
         );
     }
+
+    #[test]
+    fn min_length_from_k() {
+        let speed = DesignSpeed::mph(60.);
+        assert_eq!(min_vertical_curve_length(speed, 4.0, true), Some(604.0));
+        assert_eq!(min_vertical_curve_length(speed, -4.0, false), Some(544.0));
+        assert_eq!(
+            min_vertical_curve_length(DesignSpeed::mph(62.), 4.0, true),
+            None
+        );
+    }
+
+    #[test]
+    fn profile_points_follow_curve() {
+        let data = VerticalData {
+            input_method: VerticalDefinition::PVI,
+            input_station: "10284+50".to_string(),
+            input_elevation: "1001.38".to_string(),
+            input_incoming_grade: "0.44%".to_string(),
+            input_outgoing_grade: "-0.57%".to_string(),
+            input_length: "500".to_string(),
+            ..Default::default()
+        };
+        let curve = data.to_vertical_curve().unwrap();
+        let points = curve.profile_points(50.0);
+
+        assert_eq!(points.first().unwrap().value, curve.stations.pvc.value);
+        assert_eq!(points.last().unwrap().value, curve.stations.pvt.value);
+        assert!(points
+            .iter()
+            .any(|point| point.value == curve.get_extreme().value));
+        for point in points {
+            assert_eq!(point.elevation, curve.elevation_at(point.value));
+        }
+    }
+
+    #[test]
+    fn grade_table_spans_grades() {
+        let curve = VerticalData {
+            input_method: VerticalDefinition::PVI,
+            input_station: "10284+50".to_string(),
+            input_elevation: "1001.38".to_string(),
+            input_incoming_grade: "0.44%".to_string(),
+            input_outgoing_grade: "-0.57%".to_string(),
+            input_length: "500".to_string(),
+            ..Default::default()
+        }
+        .to_vertical_curve()
+        .unwrap();
+        let table = curve.grade_table(100.0);
+        let (first, last) = (table.first().unwrap(), table.last().unwrap());
+
+        assert_eq!(table.len(), 6);
+        assert_eq!(first.0.value, curve.stations.pvc.value);
+        assert_eq!(first.1, curve.stations.pvc.elevation.unwrap());
+        assert!((first.2 - curve.dimensions.incoming_grade).abs() < 1e-12);
+        assert_eq!(last.0.value, curve.stations.pvt.value);
+        assert!((last.2 - curve.dimensions.outgoing_grade).abs() < 1e-12);
+        assert!(table[1..table.len() - 1]
+            .iter()
+            .all(|row| row.0.value % 100.0 == 0.0));
+    }
+
+    #[test]
+    fn sag_headlight_and_comfort() {
+        // Green Book 60 mph: SSD 570 ft, sag K = 136.
+        let data = VerticalData {
+            input_method: VerticalDefinition::PVI,
+            input_station: "100+00".to_string(),
+            input_elevation: "1000".to_string(),
+            input_incoming_grade: "-4%".to_string(),
+            input_outgoing_grade: "4%".to_string(),
+            input_length: "1088".to_string(),
+            ..Default::default()
+        };
+        let sag = data.to_vertical_curve().unwrap();
+        let s = sag.sag_sight_distance_headlight().unwrap();
+        assert!((s - 570.0).abs() < 5.0);
+        assert!((sag.sag_comfort_length(DesignSpeed::mph(60.)) - 8.0 * 3600.0 / 46.5).abs() < 1e-9);
+
+        // S > L: 200 = 2S - (400 + 3.5S) / 2
+        let flat = VerticalData {
+            input_incoming_grade: "-1%".to_string(),
+            input_outgoing_grade: "1%".to_string(),
+            input_length: "200".to_string(),
+            ..data.clone()
+        }
+        .to_vertical_curve()
+        .unwrap();
+        assert!((flat.sag_sight_distance_headlight().unwrap() - 1600.0).abs() < 1e-6);
+
+        let crest = VerticalData {
+            input_incoming_grade: "4%".to_string(),
+            input_outgoing_grade: "-4%".to_string(),
+            ..data
+        }
+        .to_vertical_curve()
+        .unwrap();
+        assert!(crest.sag_sight_distance_headlight().is_none());
+    }
+
+    #[test]
+    fn two_percent_over_500_ft() {
+        let a = Station {
+            value: 1000.0,
+            elevation: Some(250.0),
+            ..Default::default()
+        };
+        let b = Station {
+            value: 1500.0,
+            elevation: Some(260.0),
+            ..Default::default()
+        };
+
+        assert!((grade_between(&a, &b).unwrap() - 0.02).abs() < 1e-12);
+        assert!((grade_between(&b, &a).unwrap() - 0.02).abs() < 1e-12);
+        assert_eq!(grade_between(&a, &a), None);
+        let unleveled = Station {
+            elevation: None,
+            ..b
+        };
+        assert_eq!(grade_between(&a, &unleveled), None);
+    }
+
+    #[test]
+    fn truck_sees_farther_over_crest() {
+        let crest = VerticalData {
+            input_method: VerticalDefinition::PVI,
+            input_station: "20+00".to_string(),
+            input_elevation: "100".to_string(),
+            input_incoming_grade: "3%".to_string(),
+            input_outgoing_grade: "-2%".to_string(),
+            input_length: "600".to_string(),
+            ..Default::default()
+        }
+        .to_vertical_curve()
+        .unwrap();
+        let car = crest
+            .available_sight_distance(SightHeights::default())
+            .unwrap();
+        let truck = crest
+            .available_sight_distance(SightHeights::truck())
+            .unwrap();
+
+        // S < L: 600 = 5 * S² / 2158.
+        assert!((car - (600.0 * 2158.0 / 5.0_f64).sqrt()).abs() < 0.5);
+        assert!(truck > car);
+
+        let sag = VerticalData {
+            input_incoming_grade: "-3%".to_string(),
+            input_outgoing_grade: "2%".to_string(),
+            input_station: "20+00".to_string(),
+            input_elevation: "100".to_string(),
+            input_length: "600".to_string(),
+            ..Default::default()
+        }
+        .to_vertical_curve()
+        .unwrap();
+        assert_eq!(sag.available_sight_distance(SightHeights::truck()), None);
+    }
 }
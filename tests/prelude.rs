@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod prelude_tests {
+    use floracad::prelude::*;
+
+    #[test]
+    fn prelude_imports() {
+        let horizontal_data = HorizontalData {
+            input_station_method: HorizontalStationDefinition::PC,
+            input_build_method: HorizontalBuildDefinition::RadiusCurveAngle,
+            input_station: "100+00".to_string(),
+            input_radius: "818.5".to_string(),
+            input_curve_angle: "63d15\'34\"".to_string(),
+            turn: TurnDirection::Right,
+            input_sight_type: SightType::Stopping,
+            input_design_standard: DesignStandard::CALTRANS,
+            input_unit_system: UnitSystem::US,
+            ..Default::default()
+        };
+        let curve: HorizontalCurve = horizontal_data.to_horizontal_curve().unwrap();
+        let _: StakeoutTable = curve.stakeout_table(25.0);
+        let _: Angle = curve.dimensions.curve_angle;
+        let _: Station = curve.stations.pc;
+        let _: Coordinate = (0., 0.);
+        let _: Option<GridTransform> = None;
+        let _: CurveSummaryRow = curve.summary_row();
+        let _: Option<SuperelevationProfile> = None;
+        let _: CurveCheck = curve.check_minimum_length_for_appearance();
+        let _ = StationSnapping::FromPc;
+        let _ = AngleFormat::Gon;
+        let _ = CurveDefinition::Chord;
+        let _: String = curve.report(&DesignCriteria::default());
+        let _: Option<SightError> = None;
+        let _: Option<CompoundCurve> = None;
+        let _ = (
+            VerticalData::default(),
+            Profile::default(),
+            Alignment::default(),
+            SsdParameters::default(),
+            DesignSpeed::mph(65.),
+        );
+    }
+}